@@ -0,0 +1,161 @@
+//! Reports the number of Miden VM cycles spent by procedures of the Tip5 library.
+//!
+//! Run using `cargo bench --bench cycles`.
+
+use miden_vm::crypto::Rpo256;
+use miden_vm::execute_iter;
+use miden_vm::math::StarkField;
+use miden_vm::AdviceInputs;
+use miden_vm::MemAdviceProvider;
+use miden_vm::StackInputs;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use zkhack_lisbon::assembler_with;
+use zkhack_lisbon::lookup_table_advice;
+use zkhack_lisbon::MdsStrategy;
+use zkhack_lisbon::PermutationParameters;
+use zkhack_lisbon::Tip5Library;
+
+/// The number of cycles spent executing the given program body, not counting the cycles any
+/// program spends regardless of its body.
+fn cycles(library: &Tip5Library, body: &str, advice_inputs: AdviceInputs) -> u32 {
+    let imports = library
+        .sources()
+        .into_iter()
+        .map(|(path, _)| format!("use.{}::{path}", Tip5Library::NAMESPACE))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let cycles_of_program = |body: &str, advice_inputs| {
+        let source = format!("{imports} begin push.0 drop {body} end");
+        let program = assembler_with(library).compile(source).unwrap();
+        let advice_provider = MemAdviceProvider::from(advice_inputs);
+        let states = execute_iter(&program, StackInputs::default(), advice_provider);
+        states.map(|state| state.unwrap().clk).last().unwrap()
+    };
+    cycles_of_program(body, advice_inputs) - cycles_of_program("", AdviceInputs::default())
+}
+
+/// Alternatives to Tip5's parameters, each with a description.
+fn alternative_parameters() -> Vec<(String, PermutationParameters)> {
+    let tip5 = PermutationParameters::tip5();
+    let rpo_mds_first_column = Rpo256::MDS.map(|row| BFieldElement::new(row[0].as_int()));
+    let mut alternatives = vec![];
+    for num_split_and_lookup in [2, 6] {
+        let parameters = PermutationParameters::new(
+            "Tip5",
+            tip5.state_size(),
+            num_split_and_lookup,
+            tip5.mds_first_column().to_vec(),
+        );
+        let description = format!("16 elements, {num_split_and_lookup} lookups");
+        alternatives.push((description, parameters));
+    }
+    let parameters = PermutationParameters::new("Tip5", 12, 4, rpo_mds_first_column.to_vec());
+    alternatives.push(("12 elements, 4 lookups".to_string(), parameters));
+    alternatives
+}
+
+/// Ways of splitting the u32 value on top of the stack into its four bytes, each with a
+/// description.
+fn byte_splits() -> [(&'static str, String); 3] {
+    let divmod = |variant| {
+        format!(
+            "u32{variant}_divmod.65536 u32{variant}_divmod.256 \
+             movup.2 u32{variant}_divmod.256"
+        )
+    };
+    let byte = |shift| format!("dup u32unchecked_shr.{shift} push.255 u32checked_and swap");
+    let shift_and_mask = format!(
+        "dup push.255 u32checked_and swap {} {} u32unchecked_shr.24",
+        byte(8),
+        byte(16),
+    );
+    [
+        ("u32checked_divmod", divmod("checked")),
+        ("u32unchecked_divmod", divmod("unchecked")),
+        ("u32unchecked_shr, u32checked_and", shift_and_mask),
+    ]
+}
+
+fn main() {
+    let library = Tip5Library::default();
+    let arithmetic_library = Tip5Library::default().with_arithmetic_lookup(true);
+    let unrolled_library = Tip5Library::default().with_mds_strategy(MdsStrategy::Unrolled);
+    let tip4_like_library = Tip5Library::default().with_tip4_like(true);
+    let mds = "exec.permutation::mds";
+    let permute = "exec.permutation::permute";
+    let measurements = [
+        (
+            "init",
+            cycles(&library, "exec.permutation::init", AdviceInputs::default()),
+        ),
+        (
+            "init_from_advice",
+            cycles(
+                &library,
+                "exec.permutation::init_from_advice",
+                lookup_table_advice(),
+            ),
+        ),
+        (
+            "sbox_layer",
+            cycles(
+                &library,
+                "exec.permutation::sbox_layer",
+                AdviceInputs::default(),
+            ),
+        ),
+        (
+            "mds (unrolled)",
+            cycles(&unrolled_library, mds, AdviceInputs::default()),
+        ),
+        ("mds", cycles(&library, mds, AdviceInputs::default())),
+        (
+            "permute (table lookup)",
+            cycles(&library, permute, AdviceInputs::default()),
+        ),
+        (
+            "permute (unrolled mds)",
+            cycles(&unrolled_library, permute, AdviceInputs::default()),
+        ),
+        (
+            "permute (arithmetic lookup)",
+            cycles(&arithmetic_library, permute, AdviceInputs::default()),
+        ),
+        (
+            "permute_isolated (arithmetic)",
+            cycles(
+                &arithmetic_library,
+                "call.permutation::permute_isolated",
+                AdviceInputs::default(),
+            ),
+        ),
+        (
+            "tip4_like::permute",
+            cycles(
+                &tip4_like_library,
+                "exec.tip4_like::permute",
+                AdviceInputs::default(),
+            ),
+        ),
+    ];
+
+    println!("{:<28} {:>8}", "procedure", "cycles");
+    for (procedure, cycles) in measurements {
+        println!("{procedure:<28} {cycles:>8}");
+    }
+
+    println!("\n{:<28} {:>8}", "permute with parameters", "cycles");
+    for (description, parameters) in alternative_parameters() {
+        let library = Tip5Library::default().with_parameters(parameters);
+        let cycles = cycles(&library, permute, AdviceInputs::default());
+        println!("{description:<28} {cycles:>8}");
+    }
+
+    println!("\n{:<33} {:>8}", "split u32 into bytes with", "cycles");
+    for (description, split) in byte_splits() {
+        let body = format!("push.3735928559 {split} dropw");
+        let cycles = cycles(&library, &body, AdviceInputs::default());
+        println!("{description:<33} {cycles:>8}");
+    }
+}
@@ -0,0 +1,22 @@
+//! Regenerates the Miden assembly modules checked in under `src/masm` using the typed builder.
+//!
+//! Run using `cargo run --example generate_masm`.
+
+use zkhack_lisbon::hash_module;
+use zkhack_lisbon::permutation_module;
+use zkhack_lisbon::sponge_module;
+use zkhack_lisbon::tip4_like_hash_module;
+
+fn main() {
+    let modules = [
+        ("permutation", permutation_module()),
+        ("sponge", sponge_module()),
+        ("hash", hash_module()),
+        ("tip4_like_hash", tip4_like_hash_module()),
+    ];
+    for (name, source) in modules {
+        let path = format!("{}/src/masm/{name}.masm", env!("CARGO_MANIFEST_DIR"));
+        std::fs::write(&path, source).expect("module must be writable");
+        println!("Wrote {path}");
+    }
+}
@@ -0,0 +1,44 @@
+//! Verifies a proof bundle written by `zkhack-lisbon prove`, in either encoding. The bundle's
+//! program hash is compared against the hash of the Tip5 program with the number of rounds
+//! recorded in the bundle. For Tip5's five rounds, that hash is a constant, so the Tip5 library is
+//! not compiled.
+//!
+//! Run using `cargo run --bin verify-bundle -- <bundle>`.
+
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+
+use zkhack_lisbon::ProofBundle;
+use zkhack_lisbon::State;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args_os().nth(1) else {
+        eprintln!("Usage: verify-bundle <bundle>");
+        return ExitCode::FAILURE;
+    };
+    match verify_bundle(Path::new(&path)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify_bundle(path: &Path) -> Result<(), Box<dyn Error>> {
+    let bundle = ProofBundle::decode(&std::fs::read(path)?)?;
+    let proof = bundle.permutation_proof()?;
+    let security_level = proof.verify()?;
+    println!("Library version: {}", bundle.library_version());
+    println!("Rounds:          {}", bundle.num_rounds());
+    println!("Input state:     {}", format_state(&proof.input_state()));
+    println!("Output state:    {}", format_state(&proof.output_state()));
+    println!("Execution verified with {security_level} bits of security!");
+    Ok(())
+}
+
+fn format_state(state: &State) -> String {
+    let values = state.iter().map(|element| element.value().to_string());
+    values.collect::<Vec<_>>().join(",")
+}
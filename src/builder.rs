@@ -0,0 +1,605 @@
+//! A typed builder for Miden assembly source. The modules of the Tip5 library are generated using
+//! it, see [`permutation_module`](crate::permutation_module) and
+//! [`hash_module`](crate::hash_module).
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// The column at which trailing comments start, unless the code preceding them is longer.
+const COMMENT_COLUMN: usize = 32;
+
+/// The indentation of every nesting level.
+const INDENTATION: &str = "    ";
+
+/// A single Miden assembly instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// `push.value`
+    Push(u64),
+    /// `push.NAME`, pushing the value of the module constant `NAME`.
+    PushConstant(&'static str),
+    /// `push.a.b.c.d`, leaving `d` on top of the stack.
+    PushWord([u64; 4]),
+    /// `dup`, the short form of `dup.0`.
+    DupTop,
+    /// `dup.index`
+    Dup(usize),
+    /// `swap.index`
+    Swap(usize),
+    /// `swapw.index`, or `swapw` if `index` is 1.
+    SwapW(usize),
+    /// `movup.index`
+    MovUp(usize),
+    /// `movdn.index`
+    MovDn(usize),
+    /// `movdnw.index`
+    MovDnW(usize),
+    /// `drop`
+    Drop,
+    /// `dropw`
+    DropW,
+    /// `padw`
+    PadW,
+    /// `add`
+    Add,
+    /// `add.value`
+    AddImm(u64),
+    /// `sub`
+    Sub,
+    /// `sub.value`
+    SubImm(u64),
+    /// `mul`
+    Mul,
+    /// `mul.value`
+    MulImm(u64),
+    /// `div.value`
+    DivImm(u64),
+    /// `eq`
+    Eq,
+    /// `neq`
+    Neq,
+    /// `neq.value`
+    NeqImm(u64),
+    /// `assert_eq`
+    AssertEq,
+    /// `assert_eqw`
+    AssertEqW,
+    /// `u32split`
+    U32Split,
+    /// `u32checked_divmod.divisor`
+    U32CheckedDivmodImm(u32),
+    /// `u32unchecked_divmod.divisor`
+    U32UncheckedDivmodImm(u32),
+    /// `u32unchecked_mod.divisor`
+    U32UncheckedModImm(u32),
+    /// `u32unchecked_gt`
+    U32UncheckedGt,
+    /// `mem_load`
+    MemLoad,
+    /// `mem_load.address`
+    MemLoadImm(u32),
+    /// `mem_store`
+    MemStore,
+    /// `mem_store.address`
+    MemStoreImm(u32),
+    /// `loc_load.index`
+    LocLoad(u16),
+    /// `loc_store.index`
+    LocStore(u16),
+    /// `loc_loadw.index`
+    LocLoadW(u16),
+    /// `loc_storew.index`
+    LocStoreW(u16),
+    /// `adv_pipe`
+    AdvPipe,
+    /// `exec.procedure`
+    Exec(String),
+}
+
+impl Instruction {
+    /// `exec.procedure`
+    pub fn exec(procedure: impl Into<String>) -> Self {
+        Self::Exec(procedure.into())
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Push(value) => write!(f, "push.{value}"),
+            Self::PushConstant(name) => write!(f, "push.{name}"),
+            Self::PushWord([a, b, c, d]) => write!(f, "push.{a}.{b}.{c}.{d}"),
+            Self::DupTop => write!(f, "dup"),
+            Self::Dup(index) => write!(f, "dup.{index}"),
+            Self::Swap(index) => write!(f, "swap.{index}"),
+            Self::SwapW(1) => write!(f, "swapw"),
+            Self::SwapW(index) => write!(f, "swapw.{index}"),
+            Self::MovUp(index) => write!(f, "movup.{index}"),
+            Self::MovDn(index) => write!(f, "movdn.{index}"),
+            Self::MovDnW(index) => write!(f, "movdnw.{index}"),
+            Self::Drop => write!(f, "drop"),
+            Self::DropW => write!(f, "dropw"),
+            Self::PadW => write!(f, "padw"),
+            Self::Add => write!(f, "add"),
+            Self::AddImm(value) => write!(f, "add.{value}"),
+            Self::Sub => write!(f, "sub"),
+            Self::SubImm(value) => write!(f, "sub.{value}"),
+            Self::Mul => write!(f, "mul"),
+            Self::MulImm(value) => write!(f, "mul.{value}"),
+            Self::DivImm(value) => write!(f, "div.{value}"),
+            Self::Eq => write!(f, "eq"),
+            Self::Neq => write!(f, "neq"),
+            Self::NeqImm(value) => write!(f, "neq.{value}"),
+            Self::AssertEq => write!(f, "assert_eq"),
+            Self::AssertEqW => write!(f, "assert_eqw"),
+            Self::U32Split => write!(f, "u32split"),
+            Self::U32CheckedDivmodImm(divisor) => write!(f, "u32checked_divmod.{divisor}"),
+            Self::U32UncheckedDivmodImm(divisor) => write!(f, "u32unchecked_divmod.{divisor}"),
+            Self::U32UncheckedModImm(divisor) => write!(f, "u32unchecked_mod.{divisor}"),
+            Self::U32UncheckedGt => write!(f, "u32unchecked_gt"),
+            Self::MemLoad => write!(f, "mem_load"),
+            Self::MemLoadImm(address) => write!(f, "mem_load.{address}"),
+            Self::MemStore => write!(f, "mem_store"),
+            Self::MemStoreImm(address) => write!(f, "mem_store.{address}"),
+            Self::LocLoad(index) => write!(f, "loc_load.{index}"),
+            Self::LocStore(index) => write!(f, "loc_store.{index}"),
+            Self::LocLoadW(index) => write!(f, "loc_loadw.{index}"),
+            Self::LocStoreW(index) => write!(f, "loc_storew.{index}"),
+            Self::AdvPipe => write!(f, "adv_pipe"),
+            Self::Exec(procedure) => write!(f, "exec.{procedure}"),
+        }
+    }
+}
+
+/// A sequence of lines of instructions, comments, and nested blocks, e.g., a procedure's body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MasmBlock {
+    statements: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Statement {
+    Line {
+        instructions: Vec<Instruction>,
+        comment: Option<String>,
+        first_width: usize,
+    },
+    Comment(String),
+    BlankLine,
+    Repeat {
+        count: usize,
+        body: MasmBlock,
+    },
+    IfTrue {
+        body: MasmBlock,
+        else_body: Option<MasmBlock>,
+    },
+    WhileTrue {
+        body: MasmBlock,
+    },
+}
+
+impl MasmBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a line holding the given instructions.
+    pub fn line(&mut self, instructions: impl IntoIterator<Item = Instruction>) -> &mut Self {
+        self.statements.push(Statement::Line {
+            instructions: instructions.into_iter().collect(),
+            comment: None,
+            first_width: 0,
+        });
+        self
+    }
+
+    /// Appends a line holding the given instructions, padding the first one to the given width.
+    /// This aligns the remaining instructions of consecutive lines, e.g., after pushing constants
+    /// of varying length.
+    pub fn aligned_line(
+        &mut self,
+        instructions: impl IntoIterator<Item = Instruction>,
+        first_width: usize,
+    ) -> &mut Self {
+        self.statements.push(Statement::Line {
+            instructions: instructions.into_iter().collect(),
+            comment: None,
+            first_width,
+        });
+        self
+    }
+
+    /// Appends a line holding the given instructions, followed by a comment, typically describing
+    /// the stack after the instructions.
+    pub fn commented_line(
+        &mut self,
+        instructions: impl IntoIterator<Item = Instruction>,
+        comment: &str,
+    ) -> &mut Self {
+        self.statements.push(Statement::Line {
+            instructions: instructions.into_iter().collect(),
+            comment: Some(comment.to_string()),
+            first_width: 0,
+        });
+        self
+    }
+
+    /// Appends a comment, which may span multiple lines.
+    pub fn comment(&mut self, comment: &str) -> &mut Self {
+        self.statements
+            .push(Statement::Comment(comment.to_string()));
+        self
+    }
+
+    /// Appends an empty line.
+    pub fn blank_line(&mut self) -> &mut Self {
+        self.statements.push(Statement::BlankLine);
+        self
+    }
+
+    /// Appends a `repeat` block executing the given body `count` times.
+    pub fn repeat(&mut self, count: usize, body: MasmBlock) -> &mut Self {
+        self.statements.push(Statement::Repeat { count, body });
+        self
+    }
+
+    /// Appends an `if.true` block executing the given body if the top of the stack is 1.
+    pub fn if_true(&mut self, body: MasmBlock) -> &mut Self {
+        let else_body = None;
+        self.statements.push(Statement::IfTrue { body, else_body });
+        self
+    }
+
+    /// Appends an `if.true` block executing the first body if the top of the stack is 1, and the
+    /// second body otherwise.
+    pub fn if_else(&mut self, body: MasmBlock, else_body: MasmBlock) -> &mut Self {
+        let else_body = Some(else_body);
+        self.statements.push(Statement::IfTrue { body, else_body });
+        self
+    }
+
+    /// Appends a `while.true` block executing the given body as long as the top of the stack is
+    /// 1. The condition is popped before every iteration.
+    pub fn while_true(&mut self, body: MasmBlock) -> &mut Self {
+        self.statements.push(Statement::WhileTrue { body });
+        self
+    }
+
+    /// Appends all statements of the given block.
+    pub fn append(&mut self, block: MasmBlock) -> &mut Self {
+        self.statements.extend(block.statements);
+        self
+    }
+
+    fn write(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        let indentation = INDENTATION.repeat(depth);
+        for statement in &self.statements {
+            match statement {
+                Statement::Line {
+                    instructions,
+                    comment,
+                    first_width,
+                } => {
+                    let mut instructions = instructions.iter().map(|i| i.to_string());
+                    let first = instructions.next().unwrap_or_default();
+                    let first = format!("{first:<first_width$}");
+                    let code = [first].into_iter().chain(instructions);
+                    let code = code.collect::<Vec<_>>().join(" ");
+                    let code = code.trim_end();
+                    write_line(f, &format!("{indentation}{code}"), comment.as_deref())?;
+                }
+                Statement::Comment(comment) => write_comment(f, &indentation, comment)?,
+                Statement::BlankLine => writeln!(f)?,
+                Statement::Repeat { count, body } => {
+                    writeln!(f, "{indentation}repeat.{count}")?;
+                    body.write(f, depth + 1)?;
+                    writeln!(f, "{indentation}end")?;
+                }
+                Statement::IfTrue { body, else_body } => {
+                    writeln!(f, "{indentation}if.true")?;
+                    body.write(f, depth + 1)?;
+                    if let Some(else_body) = else_body {
+                        writeln!(f, "{indentation}else")?;
+                        else_body.write(f, depth + 1)?;
+                    }
+                    writeln!(f, "{indentation}end")?;
+                }
+                Statement::WhileTrue { body } => {
+                    writeln!(f, "{indentation}while.true")?;
+                    body.write(f, depth + 1)?;
+                    writeln!(f, "{indentation}end")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A procedure of a [`MasmModule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MasmProcedure {
+    name: String,
+    exported: bool,
+    num_locals: u16,
+    comment: Option<String>,
+    stack_comment: Option<String>,
+    body: MasmBlock,
+}
+
+impl MasmProcedure {
+    /// A procedure that is not exported and has no locals.
+    pub fn new(name: impl Into<String>, body: MasmBlock) -> Self {
+        Self {
+            name: name.into(),
+            exported: false,
+            num_locals: 0,
+            comment: None,
+            stack_comment: None,
+            body,
+        }
+    }
+
+    /// Exports the procedure from its module.
+    pub fn exported(self) -> Self {
+        Self {
+            exported: true,
+            ..self
+        }
+    }
+
+    /// Reserves the given number of local memory addresses for the procedure.
+    pub fn with_locals(self, num_locals: u16) -> Self {
+        Self { num_locals, ..self }
+    }
+
+    /// Precedes the procedure with the given comment, which may span multiple lines.
+    pub fn with_comment(self, comment: &str) -> Self {
+        Self {
+            comment: Some(comment.to_string()),
+            ..self
+        }
+    }
+
+    /// Follows the procedure's declaration with the given comment, typically describing the
+    /// stack on entry.
+    pub fn with_stack_comment(self, stack_comment: &str) -> Self {
+        Self {
+            stack_comment: Some(stack_comment.to_string()),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for MasmProcedure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(comment) = &self.comment {
+            write_comment(f, "", comment)?;
+        }
+        let keyword = if self.exported { "export" } else { "proc" };
+        let declaration = match self.num_locals {
+            0 => format!("{keyword}.{}", self.name),
+            num_locals => format!("{keyword}.{}.{num_locals}", self.name),
+        };
+        write_line(f, &declaration, self.stack_comment.as_deref())?;
+        self.body.write(f, 1)?;
+        writeln!(f, "end")
+    }
+}
+
+/// A Miden assembly module consisting of imports, constants, and procedures, in that order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MasmModule {
+    imports: Vec<String>,
+    constants: Vec<(String, u64, Option<String>)>,
+    procedures: Vec<MasmProcedure>,
+}
+
+impl MasmModule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Imports the module with the given path, e.g., `std::math::u64`.
+    pub fn with_import(mut self, path: &str) -> Self {
+        self.imports.push(path.to_string());
+        self
+    }
+
+    /// Declares the constant `name` with the given value.
+    pub fn with_constant(mut self, name: &str, value: u64) -> Self {
+        self.constants.push((name.to_string(), value, None));
+        self
+    }
+
+    /// Declares the constant `name` with the given value, followed by a comment.
+    pub fn with_commented_constant(mut self, name: &str, value: u64, comment: &str) -> Self {
+        let comment = Some(comment.to_string());
+        self.constants.push((name.to_string(), value, comment));
+        self
+    }
+
+    /// Appends the given procedure. Procedures can only invoke procedures preceding them.
+    pub fn with_procedure(mut self, procedure: MasmProcedure) -> Self {
+        self.procedures.push(procedure);
+        self
+    }
+
+    /// Appends the given procedures in order.
+    pub fn with_procedures(mut self, procedures: impl IntoIterator<Item = MasmProcedure>) -> Self {
+        self.procedures.extend(procedures);
+        self
+    }
+
+    pub fn procedures(&self) -> &[MasmProcedure] {
+        &self.procedures
+    }
+
+    /// The source of a program executing the given body. The module's imports, constants, and
+    /// procedures precede it, with all procedures being local to the program. This allows the
+    /// program to invoke procedures the module does not export, e.g., in tests.
+    pub fn program(&self, body: &MasmBlock) -> String {
+        let mut module = self.clone();
+        for procedure in &mut module.procedures {
+            procedure.exported = false;
+        }
+        format!("{module}\n{}", Program(body))
+    }
+}
+
+/// The `begin` block of a program, see [`MasmModule::program`].
+struct Program<'a>(&'a MasmBlock);
+
+impl Display for Program<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "begin")?;
+        self.0.write(f, 1)?;
+        writeln!(f, "end")
+    }
+}
+
+impl Display for MasmModule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for path in &self.imports {
+            writeln!(f, "use.{path}")?;
+        }
+        if !self.imports.is_empty() && !self.constants.is_empty() {
+            writeln!(f)?;
+        }
+        for (name, value, comment) in &self.constants {
+            write_line(f, &format!("const.{name}={value}"), comment.as_deref())?;
+        }
+        let has_header = !self.imports.is_empty() || !self.constants.is_empty();
+        for (index, procedure) in self.procedures.iter().enumerate() {
+            if index > 0 || has_header {
+                writeln!(f)?;
+            }
+            write!(f, "{procedure}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_line(f: &mut Formatter<'_>, code: &str, comment: Option<&str>) -> fmt::Result {
+    match comment {
+        Some(comment) => writeln!(f, "{code:<width$} # {comment}", width = COMMENT_COLUMN - 1),
+        None => writeln!(f, "{code}"),
+    }
+}
+
+fn write_comment(f: &mut Formatter<'_>, indentation: &str, comment: &str) -> fmt::Result {
+    for line in comment.lines() {
+        match line {
+            "" => writeln!(f, "{indentation}#")?,
+            line => writeln!(f, "{indentation}# {line}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_comments_are_aligned() {
+        let mut body = MasmBlock::new();
+        body.commented_line([Instruction::DupTop, Instruction::MulImm(7)], "_ x 7x")
+            .line([Instruction::Drop]);
+        let procedure = MasmProcedure::new("seven", body).with_stack_comment("_ x");
+        let expected = "\
+proc.seven                      # _ x
+    dup mul.7                   # _ x 7x
+    drop
+end
+";
+        assert_eq!(expected, procedure.to_string());
+    }
+
+    #[test]
+    fn aligned_lines_form_columns() {
+        let mut body = MasmBlock::new();
+        body.aligned_line([Instruction::Push(7), Instruction::Dup(0)], 8)
+            .aligned_line([Instruction::Push(254), Instruction::DupTop], 8);
+        let procedure = MasmProcedure::new("table", body);
+        let expected = "\
+proc.table
+    push.7   dup.0
+    push.254 dup
+end
+";
+        assert_eq!(expected, procedure.to_string());
+    }
+
+    #[test]
+    fn nested_blocks_are_indented() {
+        let mut inner = MasmBlock::new();
+        inner
+            .comment("twice\n\nper iteration")
+            .line([Instruction::exec("square")]);
+        let mut body = MasmBlock::new();
+        body.repeat(2, inner)
+            .blank_line()
+            .line([Instruction::SwapW(1)]);
+        let procedure = MasmProcedure::new("pow4", body).exported().with_locals(1);
+        let module = MasmModule::new()
+            .with_import("std::math::u64")
+            .with_commented_constant("ONE", 1, "unused")
+            .with_procedure(procedure);
+        let expected = "\
+use.std::math::u64
+
+const.ONE=1                     # unused
+
+export.pow4.1
+    repeat.2
+        # twice
+        #
+        # per iteration
+        exec.square
+    end
+
+    swapw
+end
+";
+        assert_eq!(expected, module.to_string());
+    }
+
+    #[test]
+    fn programs_make_all_procedures_local() {
+        let mut body = MasmBlock::new();
+        body.line([Instruction::NeqImm(0)]);
+        let mut else_body = MasmBlock::new();
+        else_body.line([Instruction::Push(1)]);
+        let mut condition = MasmBlock::new();
+        condition.if_else(body, else_body);
+        let procedure = MasmProcedure::new("branch", condition).exported();
+        let module = MasmModule::new().with_procedure(procedure);
+        let mut main = MasmBlock::new();
+        main.line([Instruction::Push(1)])
+            .while_true(MasmBlock::new())
+            .line([Instruction::exec("branch")]);
+        let expected = "\
+proc.branch
+    if.true
+        neq.0
+    else
+        push.1
+    end
+end
+
+begin
+    push.1
+    while.true
+    end
+    exec.branch
+end
+";
+        assert_eq!(expected, module.program(&main));
+    }
+}
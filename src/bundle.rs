@@ -0,0 +1,412 @@
+use std::error::Error;
+use std::fmt;
+
+use miden_vm::math::Felt;
+use miden_vm::math::StarkField;
+use miden_vm::Digest;
+use miden_vm::ExecutionProof;
+use miden_vm::HashFunction;
+use miden_vm::Kernel;
+use miden_vm::ProgramInfo;
+use serde::Deserialize;
+use serde::Serialize;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::stack_inputs;
+use crate::stack_outputs;
+use crate::PermutationProof;
+use crate::ProofError;
+use crate::State;
+
+/// A [`PermutationProof`] in a self-contained form for storing it or sending it to a verifier. It
+/// consists of the number of rounds of the proven permutation, the hash of the proven program, the
+/// stack inputs and outputs, the serialized [`ExecutionProof`], the options the proof was
+/// generated with, and the version of this crate that generated it.
+///
+/// Bundles have a binary encoding, see [`Self::to_bytes`], and a JSON encoding, see
+/// [`Self::to_json`]. Both record [`Self::FORMAT_VERSION`], and decoding rejects bundles of any
+/// other version. Verifying a bundle of Tip5's five rounds does not compile the Tip5 library, it
+/// compares the program hash against [`TIP5_PROGRAM_HASH`](crate::TIP5_PROGRAM_HASH) instead.
+/// For other numbers of rounds, see [`PermutationProof::verify`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofBundle {
+    library_version: String,
+    num_rounds: usize,
+    program_hash: [u64; 4],
+    stack_inputs: Vec<u64>,
+    stack_outputs: Vec<u64>,
+    proof_options: RecordedProofOptions,
+    #[serde(with = "proof_bytes")]
+    proof: Vec<u8>,
+}
+
+/// The options an [`ExecutionProof`] was generated with, see
+/// [`ProofOptions`](miden_vm::ProofOptions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedProofOptions {
+    #[serde(with = "HashFunctionDef")]
+    hash_function: HashFunction,
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    field_extension_degree: u32,
+    fri_folding_factor: usize,
+    fri_remainder_max_degree: usize,
+}
+
+/// Mirrors [`HashFunction`] for serialization.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "HashFunction")]
+enum HashFunctionDef {
+    Blake3_192,
+    Blake3_256,
+    Rpo256,
+}
+
+/// The JSON encoding of a bundle, which records the format version alongside the bundle's fields.
+#[derive(Serialize)]
+struct VersionedBundle<'a> {
+    format_version: u16,
+    #[serde(flatten)]
+    bundle: &'a ProofBundle,
+}
+
+/// The part of the JSON encoding that is read before the rest, so that bundles of other format
+/// versions can be rejected before attempting to decode them.
+#[derive(Deserialize)]
+struct FormatVersion {
+    format_version: u16,
+}
+
+impl ProofBundle {
+    /// The version of the bundle format written by this crate, and the only one it reads.
+    pub const FORMAT_VERSION: u16 = 2;
+
+    /// The bytes the binary encoding of every bundle starts with.
+    pub const MAGIC: [u8; 4] = *b"T5PB";
+
+    /// The version of this crate that generated the bundle. Only informational, since
+    /// verification relies on the program hash.
+    pub fn library_version(&self) -> &str {
+        &self.library_version
+    }
+
+    /// The number of rounds of the proven permutation.
+    pub fn num_rounds(&self) -> usize {
+        self.num_rounds
+    }
+
+    /// The binary encoding of the bundle: [`Self::MAGIC`], followed by [`Self::FORMAT_VERSION`]
+    /// as a little-endian 16-bit integer, followed by the bundle's fields in
+    /// [bincode](https://github.com/bincode-org/bincode).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body = bincode::serialize(self).expect("bundle must be serializable");
+        let header = [&Self::MAGIC[..], &Self::FORMAT_VERSION.to_le_bytes()].concat();
+        [header, body].concat()
+    }
+
+    /// Decodes a bundle from its binary encoding, see [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BundleError> {
+        let body = bytes
+            .strip_prefix(&Self::MAGIC)
+            .ok_or(BundleError::UnknownEncoding)?;
+        let (format_version, body) = body
+            .split_first_chunk()
+            .ok_or(BundleError::UnknownEncoding)?;
+        let format_version = u16::from_le_bytes(*format_version);
+        if format_version != Self::FORMAT_VERSION {
+            return Err(BundleError::UnsupportedVersion(format_version));
+        }
+        Ok(bincode::deserialize(body)?)
+    }
+
+    /// The JSON encoding of the bundle: an object holding the bundle's fields and field
+    /// `format_version`. The proof is encoded as a hexadecimal string.
+    pub fn to_json(&self) -> String {
+        let bundle = VersionedBundle {
+            format_version: Self::FORMAT_VERSION,
+            bundle: self,
+        };
+        serde_json::to_string_pretty(&bundle).expect("bundle must be serializable")
+    }
+
+    /// Decodes a bundle from its JSON encoding, see [`Self::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        let FormatVersion { format_version } = serde_json::from_str(json)?;
+        if format_version != Self::FORMAT_VERSION {
+            return Err(BundleError::UnsupportedVersion(format_version));
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Decodes a bundle from either of its encodings, telling them apart by [`Self::MAGIC`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, BundleError> {
+        if bytes.starts_with(&Self::MAGIC) {
+            return Self::from_bytes(bytes);
+        }
+        let json = std::str::from_utf8(bytes).map_err(|_| BundleError::UnknownEncoding)?;
+        Self::from_json(json)
+    }
+
+    /// The proof contained in the bundle, together with the public inputs it claims to be valid
+    /// for.
+    pub fn permutation_proof(&self) -> Result<PermutationProof, BundleError> {
+        let program_hash = to_elements(&self.program_hash)?.map(|e| Felt::new(e.value()));
+        let program_info = ProgramInfo::new(Digest::new(program_hash), Kernel::default());
+        let input = to_elements(&self.stack_inputs)?;
+        let output = to_elements(&self.stack_outputs)?;
+        let proof = ExecutionProof::from_bytes(&self.proof)
+            .map_err(|error| BundleError::Malformed(format!("invalid proof: {error}")))?;
+        if RecordedProofOptions::of(&proof) != self.proof_options {
+            return Err(BundleError::OptionsMismatch);
+        }
+        let proof = PermutationProof::new(
+            self.num_rounds,
+            program_info,
+            stack_inputs(&input),
+            stack_outputs(&output),
+            proof,
+        )?;
+        Ok(proof)
+    }
+
+    /// Verifies the proof contained in the bundle, see [`PermutationProof::verify`]. Returns the
+    /// security level of the proof in bits.
+    pub fn verify(&self) -> Result<u32, BundleError> {
+        Ok(self.permutation_proof()?.verify()?)
+    }
+}
+
+impl From<&PermutationProof> for ProofBundle {
+    fn from(proof: &PermutationProof) -> Self {
+        let program_hash = proof.program_info().program_hash().as_elements();
+        let values = |state: State| state.map(|element| element.value()).to_vec();
+        Self {
+            library_version: env!("CARGO_PKG_VERSION").to_string(),
+            num_rounds: proof.num_rounds(),
+            program_hash: std::array::from_fn(|i| program_hash[i].as_int()),
+            stack_inputs: values(proof.input_state()),
+            stack_outputs: values(proof.output_state()),
+            proof_options: RecordedProofOptions::of(proof.proof()),
+            proof: proof.proof().to_bytes(),
+        }
+    }
+}
+
+impl RecordedProofOptions {
+    fn of(proof: &ExecutionProof) -> Self {
+        let options = proof.stark_proof().options();
+        let fri_options = options.to_fri_options();
+        Self {
+            hash_function: proof.hash_fn(),
+            num_queries: options.num_queries(),
+            blowup_factor: options.blowup_factor(),
+            grinding_factor: options.grinding_factor(),
+            field_extension_degree: options.field_extension().degree(),
+            fri_folding_factor: fri_options.folding_factor(),
+            fri_remainder_max_degree: fri_options.remainder_max_degree(),
+        }
+    }
+}
+
+/// Converts canonical values to the given number of field elements.
+fn to_elements<const N: usize>(values: &[u64]) -> Result<[BFieldElement; N], BundleError> {
+    if values.len() != N {
+        let message = format!("expected {N} elements, got {}", values.len());
+        return Err(BundleError::Malformed(message));
+    }
+    if let Some(value) = values.iter().find(|&&value| value >= BFieldElement::P) {
+        let message = format!("{value} is not a canonical field element");
+        return Err(BundleError::Malformed(message));
+    }
+    Ok(std::array::from_fn(|i| BFieldElement::new(values[i])))
+}
+
+/// Encodes the proof as a hexadecimal string in human-readable encodings such as JSON, and as
+/// plain bytes otherwise.
+mod proof_bytes {
+    use serde::de::Error;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            let hex = String::deserialize(deserializer)?;
+            hex::decode(hex).map_err(D::Error::custom)
+        } else {
+            Vec::deserialize(deserializer)
+        }
+    }
+}
+
+/// The reasons decoding or verifying a [`ProofBundle`] can fail.
+#[derive(Debug)]
+pub enum BundleError {
+    /// The data is in neither of the bundle's encodings.
+    UnknownEncoding,
+
+    /// The bundle is of a format version other than [`ProofBundle::FORMAT_VERSION`].
+    UnsupportedVersion(u16),
+
+    /// The binary encoding could not be decoded.
+    Binary(bincode::Error),
+
+    /// The JSON encoding could not be decoded.
+    Json(serde_json::Error),
+
+    /// The bundle was decoded, but its contents are invalid.
+    Malformed(String),
+
+    /// The proof options recorded in the bundle differ from those the proof was generated with.
+    OptionsMismatch,
+
+    /// The proof contained in the bundle is invalid.
+    Proof(ProofError),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEncoding => write!(f, "data is not a proof bundle"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "proof bundle has format version {version}, expected version {}",
+                ProofBundle::FORMAT_VERSION
+            ),
+            Self::Binary(e) => write!(f, "invalid binary proof bundle: {e}"),
+            Self::Json(e) => write!(f, "invalid JSON proof bundle: {e}"),
+            Self::Malformed(message) => write!(f, "malformed proof bundle: {message}"),
+            Self::OptionsMismatch => write!(
+                f,
+                "proof options recorded in the proof bundle differ from those of the proof"
+            ),
+            Self::Proof(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for BundleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Binary(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Proof(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<bincode::Error> for BundleError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Binary(error)
+    }
+}
+
+impl From<serde_json::Error> for BundleError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<ProofError> for BundleError {
+    fn from(error: ProofError) -> Self {
+        Self::Proof(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use miden_vm::ProofOptions;
+
+    use super::*;
+    use crate::prove_permutation;
+    use crate::test_helpers::sample_state;
+
+    /// A bundle of a proof of the permutation, generated once for all tests.
+    fn bundle() -> &'static ProofBundle {
+        static BUNDLE: OnceLock<ProofBundle> = OnceLock::new();
+        BUNDLE.get_or_init(|| {
+            let proof = prove_permutation(sample_state(1), ProofOptions::default()).unwrap();
+            ProofBundle::from(&proof)
+        })
+    }
+
+    #[test]
+    fn bundle_survives_round_trips_through_both_encodings() {
+        let bundle = bundle();
+        assert_eq!(
+            bundle,
+            &ProofBundle::from_bytes(&bundle.to_bytes()).unwrap()
+        );
+        assert_eq!(bundle, &ProofBundle::from_json(&bundle.to_json()).unwrap());
+        assert_eq!(bundle, &ProofBundle::decode(&bundle.to_bytes()).unwrap());
+        assert_eq!(
+            bundle,
+            &ProofBundle::decode(bundle.to_json().as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn bundle_verifies() {
+        assert!(bundle().verify().is_ok());
+    }
+
+    #[test]
+    fn bundles_of_other_format_versions_are_rejected() {
+        let mut bytes = bundle().to_bytes();
+        bytes[ProofBundle::MAGIC.len()] += 1;
+        let result = ProofBundle::from_bytes(&bytes);
+        assert!(matches!(result, Err(BundleError::UnsupportedVersion(3))));
+
+        let json = bundle()
+            .to_json()
+            .replace("\"format_version\": 2", "\"format_version\": 1");
+        let result = ProofBundle::from_json(&json);
+        assert!(matches!(result, Err(BundleError::UnsupportedVersion(1))));
+    }
+
+    #[test]
+    fn tampered_bundles_are_rejected() {
+        let mut other_outputs = bundle().clone();
+        other_outputs.stack_outputs[0] += 1;
+        let result = other_outputs.verify();
+        assert!(matches!(result, Err(BundleError::Proof(_))));
+
+        let mut other_options = bundle().clone();
+        other_options.proof_options.num_queries += 1;
+        let result = other_options.verify();
+        assert!(matches!(result, Err(BundleError::OptionsMismatch)));
+
+        let mut non_canonical_inputs = bundle().clone();
+        non_canonical_inputs.stack_inputs[0] = BFieldElement::P;
+        let result = non_canonical_inputs.verify();
+        assert!(matches!(result, Err(BundleError::Malformed(_))));
+
+        let mut other_num_rounds = bundle().clone();
+        other_num_rounds.num_rounds = 4;
+        let result = other_num_rounds.verify();
+        assert!(matches!(
+            result,
+            Err(BundleError::Proof(ProofError::ProgramMismatch { .. }))
+        ));
+
+        let mut unsupported_num_rounds = bundle().clone();
+        unsupported_num_rounds.num_rounds = 0;
+        let result = unsupported_num_rounds.verify();
+        assert!(matches!(
+            result,
+            Err(BundleError::Proof(ProofError::Library(_)))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Consistency checks between the constants in [`TIP5_LIB`] and those of the reference
+//! implementation in [`twenty_first`]. Unlike the differential tests, mismatches are reported
+//! with the line and procedure of the offending instruction, or with the offending entry of the
+//! MDS matrix.
+
+use miden_vm::execute;
+use miden_vm::MemAdviceProvider;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::LOOKUP_TABLE;
+use twenty_first::shared_math::tip5::MDS_MATRIX_FIRST_COLUMN;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+use twenty_first::shared_math::tip5::ROUND_CONSTANTS;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+use crate::assembler;
+use crate::stack_inputs;
+use crate::state_from_outputs;
+use crate::State;
+use crate::TIP5_LIB;
+
+/// An instruction of [`TIP5_LIB`] along with its location.
+struct Instruction {
+    line: usize,
+    procedure: &'static str,
+    text: &'static str,
+}
+
+fn instructions() -> Vec<Instruction> {
+    let mut procedure = "";
+    let mut instructions = vec![];
+    for (index, line) in TIP5_LIB.lines().enumerate() {
+        let code = line.split('#').next().unwrap();
+        for text in code.split_whitespace() {
+            let declaration = text.strip_prefix("proc.").or(text.strip_prefix("export."));
+            if let Some(declaration) = declaration {
+                procedure = declaration.split('.').next().unwrap();
+                continue;
+            }
+            let line = index + 1;
+            instructions.push(Instruction {
+                line,
+                procedure,
+                text,
+            });
+        }
+    }
+    instructions
+}
+
+/// The instructions of the given procedure that are the given operation with a numeric immediate
+/// value, along with that value.
+fn immediates(procedure: &str, operation: &str) -> Vec<(Instruction, u64)> {
+    let prefix = format!("{operation}.");
+    instructions()
+        .into_iter()
+        .filter(|instruction| instruction.procedure == procedure)
+        .filter_map(|instruction| {
+            let value = instruction.text.strip_prefix(&prefix)?.parse().ok()?;
+            Some((instruction, value))
+        })
+        .collect()
+}
+
+/// Descriptions of all instructions of the given procedure whose immediate value disagrees with
+/// the expected one. Each expected value comes with a description of its meaning.
+fn mismatches(procedure: &str, operation: &str, expected: &[(String, u64)]) -> Vec<String> {
+    let actual = immediates(procedure, operation);
+    let mut mismatches = vec![];
+    if actual.len() != expected.len() {
+        mismatches.push(format!(
+            "procedure `{procedure}` has {} `{operation}` instructions, expected {}",
+            actual.len(),
+            expected.len()
+        ));
+    }
+    for ((instruction, value), (meaning, expected_value)) in actual.iter().zip(expected) {
+        if value != expected_value {
+            mismatches.push(format!(
+                "line {} in procedure `{procedure}`: `{}` should be `{operation}.{expected_value}` \
+                ({meaning})",
+                instruction.line, instruction.text,
+            ));
+        }
+    }
+    mismatches
+}
+
+fn assert_no_mismatches(mismatches: Vec<String>) {
+    assert!(mismatches.is_empty(), "\n{}", mismatches.join("\n"));
+}
+
+#[test]
+fn lookup_table_entries_agree_with_twenty_first() {
+    let expected = LOOKUP_TABLE
+        .iter()
+        .enumerate()
+        .map(|(byte, &entry)| (format!("lookup table entry {byte}"), entry.into()))
+        .collect::<Vec<_>>();
+    assert_no_mismatches(mismatches("init", "push", &expected));
+}
+
+/// The columns of the matrix by which procedure `mds` multiplies the state, obtained by executing
+/// it on each unit vector. This is independent of how the procedure computes the product.
+fn masm_mds_columns() -> Vec<State> {
+    let source = "use.tip5::permutation begin exec.permutation::mds end";
+    let program = assembler().compile(source).unwrap();
+    (0..STATE_SIZE)
+        .map(|column| {
+            let unit_vector = std::array::from_fn(|row| BFieldElement::new((row == column).into()));
+            let stack_inputs = stack_inputs(&unit_vector);
+            let trace = execute(&program, stack_inputs, MemAdviceProvider::default()).unwrap();
+            state_from_outputs(trace.stack_outputs())
+        })
+        .collect()
+}
+
+#[test]
+fn mds_matrix_agrees_with_twenty_first() {
+    let mut mismatches = vec![];
+    for (column, actual) in masm_mds_columns().iter().enumerate() {
+        for (row, actual_entry) in actual.iter().enumerate() {
+            let index = (row + STATE_SIZE - column) % STATE_SIZE;
+            let expected_entry = BFieldElement::new(MDS_MATRIX_FIRST_COLUMN[index] as u64);
+            if *actual_entry != expected_entry {
+                mismatches.push(format!(
+                    "entry ({row}, {column}) of the MDS matrix is {}, expected {} (entry {index} \
+                    of the first column)",
+                    actual_entry.value(),
+                    expected_entry.value()
+                ));
+            }
+        }
+    }
+    assert_no_mismatches(mismatches);
+}
+
+#[test]
+fn round_constants_agree_with_twenty_first() {
+    let half = STATE_SIZE / 2;
+    let mut all_mismatches = vec![];
+    for round in 0..NUM_ROUNDS {
+        let constant = |index: usize| {
+            let meaning = format!("round constant {index} of round {round}");
+            let value = ROUND_CONSTANTS[round * STATE_SIZE + index].value();
+            (meaning, value)
+        };
+        let expected = (0..half)
+            .flat_map(|j| [constant(j + half), constant(j)])
+            .collect::<Vec<_>>();
+        all_mismatches.extend(mismatches(&format!("round_{round}"), "add", &expected));
+    }
+    assert_no_mismatches(all_mismatches);
+}
@@ -0,0 +1,592 @@
+//! Differential tests of the Miden assembly implementation of Tip5 against the reference
+//! implementation in [`twenty_first`].
+
+use miden_vm::execute;
+use miden_vm::ExecutionError;
+use miden_vm::MemAdviceProvider;
+use miden_vm::StackInputs;
+use proptest::prelude::*;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::rescue_prime_digest::Digest;
+use twenty_first::shared_math::tip5::Tip5;
+use twenty_first::shared_math::tip5::Tip5State;
+use twenty_first::shared_math::tip5::DIGEST_LENGTH;
+use twenty_first::shared_math::tip5::LOOKUP_TABLE;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+use twenty_first::shared_math::tip5::RATE;
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+use twenty_first::util_types::algebraic_hasher::Domain;
+use twenty_first::util_types::algebraic_hasher::SpongeHasher;
+
+use crate::builder::MasmBlock;
+use crate::generator::permutation_module_with;
+use crate::library::Options;
+use crate::reference::split_and_lookup;
+use crate::test_helpers::sample_state;
+use crate::test_helpers::twenty_first_permutation;
+use crate::*;
+
+/// Field elements that are particularly likely to trip up the implementation, like the
+/// boundaries of the limbs used in `split_and_lookup`.
+const EDGE_VALUES: [u64; 5] = [0, 1, BFieldElement::MAX, 1 << 32, (1 << 32) - 1];
+
+fn arbitrary_element() -> impl Strategy<Value = BFieldElement> {
+    prop_oneof![
+        any::<u64>().prop_map(BFieldElement::new),
+        prop::sample::select(EDGE_VALUES.to_vec()).prop_map(BFieldElement::new),
+        montgomery_element_with_0xff_bytes(),
+    ]
+}
+
+/// Elements whose Montgomery representation, _i.e._, the representation the lookup table is
+/// applied to, has some of its bytes set to `0xff`. Representations that are not canonical are
+/// discarded rather than reduced, which would clear the `0xff` bytes.
+fn montgomery_element_with_0xff_bytes() -> impl Strategy<Value = BFieldElement> {
+    let canonical = "Montgomery representation must be canonical";
+    (any::<u64>(), 1..=u8::MAX).prop_filter_map(canonical, |(raw, byte_mask)| {
+        let mut bytes = raw.to_le_bytes();
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            if byte_mask & (1 << i) != 0 {
+                *byte = 0xff;
+            }
+        }
+        let raw = u64::from_le_bytes(bytes);
+        (raw < BFieldElement::P).then(|| BFieldElement::from_raw_u64(raw))
+    })
+}
+
+fn arbitrary_state() -> impl Strategy<Value = State> {
+    prop::array::uniform16(arbitrary_element())
+}
+
+/// Parameters of a permutation with few rounds, along with a state of matching size.
+fn arbitrary_parameters_and_state(
+) -> impl Strategy<Value = (PermutationParameters, Vec<BFieldElement>)> {
+    let sizes = PermutationParameters::MIN_STATE_SIZE..=PermutationParameters::MAX_STATE_SIZE;
+    sizes.prop_flat_map(|size| {
+        let mds_first_column = prop::collection::vec(arbitrary_element(), size);
+        let parameters = (0..=size, mds_first_column, 1..=3_usize).prop_map(
+            move |(num_split_and_lookup, mds_first_column, num_rounds)| {
+                PermutationParameters::new(
+                    "Tip5-like",
+                    size,
+                    num_split_and_lookup,
+                    mds_first_column,
+                )
+                .with_num_rounds(num_rounds)
+            },
+        );
+        (parameters, prop::collection::vec(arbitrary_element(), size))
+    })
+}
+
+fn arbitrary_chunk() -> impl Strategy<Value = [BFieldElement; RATE]> {
+    prop::array::uniform10(arbitrary_element())
+}
+
+/// Instruction pushing the given elements such that the first one ends up on top of the stack.
+fn push_elements(elements: &[BFieldElement]) -> String {
+    let values = elements.iter().rev().map(|e| e.value().to_string());
+    format!("push.{}", values.collect::<Vec<_>>().join("."))
+}
+
+/// Executes the given program body after initializing the lookup table, returning the stack.
+fn execute_with_tip5(body: &str) -> Vec<u64> {
+    execute_with(&Tip5Library::default(), body)
+}
+
+/// Executes the given program body, which can use every module of the given library, after
+/// initializing the lookup table, returning the stack.
+fn execute_with(library: &Tip5Library, body: &str) -> Vec<u64> {
+    let imports = library
+        .sources()
+        .into_iter()
+        .map(|(path, _)| format!("use.{}::{path}", Tip5Library::NAMESPACE));
+    let source = format!(
+        "
+        {}
+
+        begin
+            exec.permutation::init
+            {body}
+        end
+        ",
+        imports.collect::<Vec<_>>().join("\n")
+    );
+    let program = assembler_with(library).compile(source).unwrap();
+    let trace = execute(&program, Default::default(), MemAdviceProvider::default()).unwrap();
+    trace.stack_outputs().stack().to_vec()
+}
+
+/// Executes the given program, which uses the given library, on the given state, returning the
+/// state on top of the stack afterwards.
+fn execute_on_state(library: &Tip5Library, source: &str, state: State) -> State {
+    let program = assembler_with(library).compile(source).unwrap();
+    let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default()).unwrap();
+    state_from_outputs(trace.stack_outputs())
+}
+
+/// The source of a program executing the given instructions, which can invoke every procedure
+/// of the permutation module, exported or not.
+fn program_with_permutation(instructions: impl IntoIterator<Item = Instruction>) -> String {
+    let mut body = MasmBlock::new();
+    body.line(instructions);
+    permutation_module_with(&Options::default()).program(&body)
+}
+
+/// The state after each of the first `num_rounds` rounds of the Miden assembly implementation.
+fn masm_state_after_rounds(state: State, num_rounds: usize) -> State {
+    let rounds = (0..num_rounds).map(|round| Instruction::exec(format!("round_{round}")));
+    let source = program_with_permutation([Instruction::exec("init")].into_iter().chain(rounds));
+    let program = assembler().compile(source).unwrap();
+    let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default()).unwrap();
+    state_from_outputs(trace.stack_outputs())
+}
+
+/// The first round after which the Miden assembly implementation and the reference
+/// implementation disagree, if any.
+fn first_divergent_round(state: State) -> Option<usize> {
+    let reference_trace = Tip5::trace(&mut Tip5State { state });
+    (1..=NUM_ROUNDS)
+        .find(|&num_rounds| {
+            masm_state_after_rounds(state, num_rounds) != reference_trace[num_rounds]
+        })
+        .map(|num_rounds| num_rounds - 1)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn masm_split_and_lookup_agrees_with_twenty_first(element in arbitrary_element()) {
+        let source = program_with_permutation([
+            Instruction::exec("init"),
+            Instruction::Push(element.value()),
+            Instruction::exec("split_and_lookup"),
+        ]);
+        let program = assembler().compile(source).unwrap();
+        let trace = execute(&program, Default::default(), MemAdviceProvider::default()).unwrap();
+        let element_from_masm = BFieldElement::new(trace.stack_outputs().stack()[0]);
+        prop_assert_eq!(split_and_lookup(element), element_from_masm);
+    }
+
+    #[test]
+    fn masm_permutation_agrees_with_twenty_first(state in arbitrary_state()) {
+        let expected_state = twenty_first_permutation(state);
+        let state_from_masm = permute(state).unwrap();
+        prop_assert_eq!(
+            expected_state,
+            state_from_masm,
+            "first divergent round: {:?}",
+            first_divergent_round(state)
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn masm_sponge_agrees_with_twenty_first(
+        chunks in prop::array::uniform2(arbitrary_chunk()),
+        is_fixed_length in any::<bool>(),
+    ) {
+        let (domain, init) = match is_fixed_length {
+            true => (Domain::FixedLength, "exec.sponge::init_fixed_length"),
+            false => (Domain::VariableLength, "exec.sponge::init_variable_length"),
+        };
+        let mut sponge = Tip5State::new(domain);
+        Tip5::absorb(&mut sponge, &chunks[0]);
+        Tip5::absorb(&mut sponge, &chunks[1]);
+        let produce = Tip5::squeeze(&mut sponge);
+
+        let stack = execute_with_tip5(&format!(
+            "{init}
+            {} exec.sponge::absorb
+            {} exec.sponge::absorb
+            exec.sponge::squeeze",
+            push_elements(&chunks[0]),
+            push_elements(&chunks[1]),
+        ));
+        prop_assert_eq!(&produce.map(|e| e.value()), &stack[..RATE]);
+        prop_assert_eq!(&sponge.state.map(|e| e.value()), &stack[RATE..RATE + 16]);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn masm_hash_10_agrees_with_twenty_first(input in arbitrary_chunk()) {
+        let stack = execute_with_tip5(&format!(
+            "{} exec.hash::hash_10",
+            push_elements(&input)
+        ));
+        let expected_digest = Tip5::hash_10(&input).map(|e| e.value());
+        prop_assert_eq!(&expected_digest, &stack[..DIGEST_LENGTH]);
+        prop_assert!(stack[DIGEST_LENGTH..].iter().all(|&e| e == 0));
+    }
+
+    #[test]
+    fn masm_hash_pair_agrees_with_twenty_first(
+        left in prop::array::uniform5(arbitrary_element()),
+        right in prop::array::uniform5(arbitrary_element()),
+    ) {
+        let stack = execute_with_tip5(&format!(
+            "{} {} exec.hash::hash_pair",
+            push_elements(&right),
+            push_elements(&left),
+        ));
+        let expected_digest = Tip5::hash_pair(&Digest::new(left), &Digest::new(right));
+        prop_assert_eq!(&expected_digest.values().map(|e| e.value()), &stack[..DIGEST_LENGTH]);
+        prop_assert!(stack[DIGEST_LENGTH..].iter().all(|&e| e == 0));
+    }
+}
+
+/// Instructions writing the given elements to consecutive memory addresses, starting at `ptr`.
+fn store_elements(elements: &[BFieldElement], ptr: u32) -> String {
+    let stores = elements
+        .iter()
+        .zip(ptr..)
+        .map(|(element, address)| format!("push.{} mem_store.{address}", element.value()));
+    stores.collect::<Vec<_>>().join(" ")
+}
+
+fn assert_masm_hash_varlen_agrees_with_twenty_first(input: &[BFieldElement]) {
+    let ptr = 1000;
+    let stack = execute_with_tip5(&format!(
+        "{} push.{}.{ptr} exec.hash::hash_varlen",
+        store_elements(input, ptr),
+        input.len(),
+    ));
+    let expected_digest = Tip5::hash_varlen(input).values().map(|e| e.value());
+    assert_eq!(
+        expected_digest,
+        stack[..DIGEST_LENGTH],
+        "length: {}",
+        input.len()
+    );
+    assert!(stack[DIGEST_LENGTH..].iter().all(|&e| e == 0));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn masm_hash_varlen_agrees_with_twenty_first(
+        input in prop::collection::vec(arbitrary_element(), 0..4 * RATE),
+    ) {
+        assert_masm_hash_varlen_agrees_with_twenty_first(&input);
+    }
+}
+
+#[test]
+fn masm_hash_varlen_agrees_with_twenty_first_around_chunk_boundaries() {
+    for length in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE] {
+        let input = (0..length as u64)
+            .map(BFieldElement::new)
+            .collect::<Vec<_>>();
+        assert_masm_hash_varlen_agrees_with_twenty_first(&input);
+    }
+}
+
+#[test]
+fn masm_permutation_agrees_with_twenty_first_on_edge_values() {
+    for value in EDGE_VALUES {
+        let state = [BFieldElement::new(value); 16];
+        let expected_state = twenty_first_permutation(state);
+        assert_eq!(expected_state, permute(state).unwrap(), "value: {value}");
+    }
+}
+
+#[test]
+fn relocated_lookup_table_leaves_low_memory_untouched() {
+    let library = Tip5Library::default().with_table_base(1 << 20);
+    let source = "
+        use.tip5::permutation
+
+        begin
+            push.42 mem_store.7
+            exec.permutation::init
+            exec.permutation::permute
+            mem_load.7 eq.42 assert
+        end
+    ";
+    let state = sample_state(0);
+    let expected_state = twenty_first_permutation(state);
+    assert_eq!(expected_state, execute_on_state(&library, source, state));
+}
+
+#[test]
+fn isolated_permutation_leaves_callers_memory_untouched() {
+    let source = "
+        use.tip5::permutation
+
+        begin
+            push.42 mem_store.7
+            call.permutation::permute_isolated
+            mem_load.7 eq.42 assert
+            mem_load.1 eq.0 assert
+        end
+    ";
+    let state = sample_state(0);
+    let expected_state = twenty_first_permutation(state);
+    for arithmetic_lookup in [false, true] {
+        let library = Tip5Library::default().with_arithmetic_lookup(arithmetic_lookup);
+        assert_eq!(expected_state, execute_on_state(&library, source, state));
+    }
+}
+
+#[test]
+fn ensure_init_writes_lookup_table_only_once() {
+    let source = "
+        use.tip5::permutation
+
+        begin
+            exec.permutation::ensure_init
+            push.42 mem_store.0
+            exec.permutation::ensure_init
+            mem_load.0 eq.42 assert
+            mem_load.1 eq.7 assert
+        end
+    ";
+    let program = assembler().compile(source).unwrap();
+    execute(&program, Default::default(), MemAdviceProvider::default()).unwrap();
+}
+
+#[test]
+fn permutation_agrees_with_twenty_first_after_repeated_ensure_init() {
+    let source = "
+        use.tip5::permutation
+
+        begin
+            exec.permutation::ensure_init
+            exec.permutation::ensure_init
+            exec.permutation::permute
+        end
+    ";
+    let state = sample_state(0);
+    let expected_state = twenty_first_permutation(state);
+    assert_eq!(
+        expected_state,
+        execute_on_state(&Tip5Library::default(), source, state)
+    );
+}
+
+#[test]
+fn table_assertions_catch_missing_initialization() {
+    let library = Tip5Library::default().with_table_assertions(true);
+    let program_with_init = |init| {
+        let source = format!("use.tip5::permutation begin {init} exec.permutation::permute end");
+        assembler_with(&library).compile(source).unwrap()
+    };
+
+    let program = program_with_init("");
+    let result = execute(&program, Default::default(), MemAdviceProvider::default());
+    assert!(matches!(result, Err(ExecutionError::FailedAssertion(_))));
+
+    let program = program_with_init("exec.permutation::init");
+    let state = sample_state(0);
+    let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default()).unwrap();
+    let expected_state = twenty_first_permutation(state);
+    assert_eq!(expected_state, state_from_outputs(trace.stack_outputs()));
+}
+
+#[test]
+fn arithmetic_lookup_agrees_with_lookup_table() {
+    let assertions = LOOKUP_TABLE.iter().enumerate().flat_map(|(byte, &entry)| {
+        [
+            Instruction::Push(byte as u64),
+            Instruction::exec("arithmetic_lookup"),
+            Instruction::Push(entry.into()),
+            Instruction::AssertEq,
+        ]
+    });
+    let source = program_with_permutation(assertions);
+    let program = assembler().compile(source).unwrap();
+    execute(&program, Default::default(), MemAdviceProvider::default()).unwrap();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn permutation_with_arithmetic_lookup_agrees_with_twenty_first(state in arbitrary_state()) {
+        let library = Tip5Library::default().with_arithmetic_lookup(true);
+        let source = "
+            use.tip5::permutation
+
+            begin
+                push.42 mem_store.0
+                exec.permutation::permute
+                mem_load.0 eq.42 assert
+                mem_load.1 eq.0 assert
+            end
+        ";
+        let expected_state = twenty_first_permutation(state);
+        prop_assert_eq!(expected_state, execute_on_state(&library, source, state));
+    }
+}
+
+#[test]
+fn no_round_diverges_for_sequential_state() {
+    let state = sample_state(0);
+    assert_eq!(None, first_divergent_round(state));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn permutation_with_unrolled_mds_agrees_with_twenty_first(state in arbitrary_state()) {
+        let library = Tip5Library::default().with_mds_strategy(MdsStrategy::Unrolled);
+        let source = "use.tip5::permutation begin exec.permutation::init exec.permutation::permute end";
+        let expected_state = twenty_first_permutation(state);
+        prop_assert_eq!(expected_state, execute_on_state(&library, source, state));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn permutation_with_any_number_of_rounds_agrees_with_reference(
+        state in arbitrary_state(),
+        num_rounds in 1..=MAX_NUM_ROUNDS,
+    ) {
+        let library = Tip5Library::default().with_num_rounds(num_rounds);
+        let program = tip5_program_with(&library);
+        let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default()).unwrap();
+        let expected_state = reference_permutation(state, num_rounds);
+        prop_assert_eq!(expected_state, state_from_outputs(trace.stack_outputs()));
+    }
+}
+
+/// Executes procedure `permute` of a library implementing the permutation with the given
+/// parameters on the given state, which must be of matching size.
+fn masm_permutation_with(
+    parameters: &PermutationParameters,
+    state: &[BFieldElement],
+) -> Vec<BFieldElement> {
+    let library = Tip5Library::default().with_parameters(parameters.clone());
+    let source = "use.tip5::permutation begin exec.permutation::init exec.permutation::permute end";
+    let program = assembler_with(&library).compile(source).unwrap();
+    let inputs = state.iter().rev().map(|element| element.value());
+    let inputs = StackInputs::try_from_values(inputs).unwrap();
+    let trace = execute(&program, inputs, MemAdviceProvider::default()).unwrap();
+    let outputs = &trace.stack_outputs().stack()[..state.len()];
+    outputs
+        .iter()
+        .map(|&element| BFieldElement::new(element))
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn tip5_like_permutation_agrees_with_reference(
+        (parameters, state) in arbitrary_parameters_and_state(),
+    ) {
+        let expected_state = reference_permutation_with(&parameters, &state);
+        prop_assert_eq!(expected_state, masm_permutation_with(&parameters, &state));
+    }
+}
+
+#[test]
+fn tip5_parameters_yield_tip5() {
+    let state = sample_state(0);
+    let expected_state = twenty_first_permutation(state);
+    let state_from_masm = masm_permutation_with(&PermutationParameters::tip5(), &state);
+    assert_eq!(expected_state.to_vec(), state_from_masm);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn masm_tip4_like_permutation_agrees_with_reference(
+        state in prop::collection::vec(arbitrary_element(), 12),
+    ) {
+        let source = "
+            use.tip5::permutation
+            use.tip5::tip4_like
+
+            begin
+                exec.permutation::init
+                exec.tip4_like::permute
+            end
+        ";
+        let library = Tip5Library::default().with_tip4_like(true);
+        let program = assembler_with(&library).compile(source).unwrap();
+        let inputs = state.iter().rev().map(|element| element.value());
+        let inputs = StackInputs::try_from_values(inputs).unwrap();
+        let trace = execute(&program, inputs, MemAdviceProvider::default()).unwrap();
+
+        let parameters = PermutationParameters::tip4_like();
+        let expected_state = reference_permutation_with(&parameters, &state);
+        let expected_state = expected_state.iter().map(|e| e.value()).collect::<Vec<_>>();
+        prop_assert_eq!(&expected_state, &trace.stack_outputs().stack()[..12]);
+    }
+
+    #[test]
+    fn masm_tip4_like_hash_pair_agrees_with_reference(
+        left in prop::array::uniform4(arbitrary_element()),
+        right in prop::array::uniform4(arbitrary_element()),
+    ) {
+        let library = Tip5Library::default().with_tip4_like(true);
+        let stack = execute_with(&library, &format!(
+            "{} {} exec.tip4_like_hash::hash_pair",
+            push_elements(&right),
+            push_elements(&left),
+        ));
+        let expected_digest = tip4_like_hash_pair(&left, &right).map(|e| e.value());
+        prop_assert_eq!(&expected_digest, &stack[..TIP4_LIKE_DIGEST_LENGTH]);
+        prop_assert!(stack[TIP4_LIKE_DIGEST_LENGTH..].iter().all(|&e| e == 0));
+    }
+
+    #[test]
+    fn masm_tip4_like_hash_varlen_agrees_with_reference(
+        input in prop::collection::vec(arbitrary_element(), 0..4 * TIP4_LIKE_RATE),
+    ) {
+        assert_masm_tip4_like_hash_varlen_agrees_with_reference(&input);
+    }
+}
+
+fn assert_masm_tip4_like_hash_varlen_agrees_with_reference(input: &[BFieldElement]) {
+    let ptr = 1000;
+    let library = Tip5Library::default().with_tip4_like(true);
+    let stack = execute_with(
+        &library,
+        &format!(
+            "{} push.{}.{ptr} exec.tip4_like_hash::hash_varlen",
+            store_elements(input, ptr),
+            input.len(),
+        ),
+    );
+    let expected_digest = tip4_like_hash_varlen(input).map(|e| e.value());
+    assert_eq!(
+        expected_digest,
+        stack[..TIP4_LIKE_DIGEST_LENGTH],
+        "length: {}",
+        input.len()
+    );
+    assert!(stack[TIP4_LIKE_DIGEST_LENGTH..].iter().all(|&e| e == 0));
+}
+
+#[test]
+fn masm_tip4_like_hash_varlen_agrees_with_reference_around_chunk_boundaries() {
+    for length in [
+        0,
+        1,
+        TIP4_LIKE_RATE - 1,
+        TIP4_LIKE_RATE,
+        TIP4_LIKE_RATE + 1,
+        2 * TIP4_LIKE_RATE,
+    ] {
+        let input = (0..length as u64)
+            .map(BFieldElement::new)
+            .collect::<Vec<_>>();
+        assert_masm_tip4_like_hash_varlen_agrees_with_reference(&input);
+    }
+}
@@ -0,0 +1,542 @@
+//! Generation of the Miden assembly source of the Tip5 permutation, [`TIP5_LIB`](crate::TIP5_LIB),
+//! from the constants of the reference implementation in [`twenty_first`].
+//!
+//! The generated source is checked in as `src/masm/permutation.masm`. Regenerate it using
+//! `cargo run --example generate_masm`.
+
+use miden_vm::crypto::Rpo256;
+use miden_vm::math::StarkField;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::LOOKUP_TABLE;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+use twenty_first::shared_math::traits::Inverse;
+
+use crate::builder::Instruction;
+use crate::builder::Instruction::*;
+use crate::builder::MasmBlock;
+use crate::builder::MasmModule;
+use crate::builder::MasmProcedure;
+use crate::library::MdsStrategy;
+use crate::library::Options;
+use crate::lookup_table_advice;
+use crate::PermutationParameters;
+use crate::Tip5Library;
+
+/// A square root of -1 in the field.
+const SQRT_MINUS_ONE: BFieldElement = BFieldElement::new(1 << 48);
+
+/// The value marking the lookup table as initialized, 'tip5' in ASCII.
+const SENTINEL: u32 = u32::from_be_bytes(*b"tip5");
+
+const INIT_FROM_ADVICE_COMMENT: &str = "\
+Like init, but reads the lookup table from the advice stack, where every entry is expected as
+a word with the entry in its first element, followed by three zeros. The entries are written
+to memory two words at a time and hashed along the way. The resulting digest must match the
+commitment to the lookup table, preventing the prover from supplying a different table.";
+
+const TABLE_LOOKUP_COMMENT: &str = "\
+Since the Tip5 initialization procedure has dumped the lookup table into addresses
+TABLE_BASE .. TABLE_BASE+255, we can simply use the memory load instruction to do the
+lookups.";
+
+const ARITHMETIC_LOOKUP_COMMENT: &str = "\
+Alternatively, the lookup table's entries can be computed without accessing memory, using
+that the entry for a byte is ((byte + 1)^3 mod 257) - 1.";
+
+const SPLIT_AND_LOOKUP_COMMENT: &str = "\
+The limbs produced by u32split are proven to be u32 values, and so are all quotients and
+remainders derived from them. The unchecked variant of u32 division is thus sufficient,
+saving one cycle per division. Shifting and masking costs more: the assembler implements a
+shift by an immediate as a division by a power of two, and u32checked_and also needs rows in
+the bitwise chiplet. The cycles bench compares the three ways of splitting a u32 into bytes.";
+
+const SBOX_LAYER_COMMENT: &str = "\
+Applies the S-box to every state element. After each element, the state is rotated by one
+element, bringing the next element to the top of the stack. The power map is computed as
+x^7 = x^4 * x^2 * x, which takes fewer cycles than exp.7.";
+
+const MDS_COMMENT: &str = "\
+Multiplies the state with the circulant MDS matrix, i.e., computes the cyclic convolution of
+the state and the matrix's first column. Since x^16 - 1 = (x^4 - 1)(x^4 + 1)(x^4 - i)(x^4 + i)
+where i = 2^48 is a square root of -1, the convolution can be computed modulo each of the
+four factors separately. Two layers of butterflies reduce the state modulo the factors, four
+products of size 4 with the correspondingly reduced first column follow, and two layers of
+butterflies reconstruct the result. The inverse transformation's factor 1/4 is folded into
+the reduced first column.
+
+This procedure stops short of the last layer of butterflies, leaving the product modulo
+x^8 - 1 and x^8 + 1 on the stack. The last layer is part of procedure mds and of the rounds,
+which fuse the addition of round constants into it.";
+
+const MDS_UNROLLED_COMMENT: &str = "\
+Multiplies the state with the circulant MDS matrix row by row, storing every row's inner
+product with the state in a local. The state is then replaced by the stored products.";
+
+const PERMUTE_ISOLATED_COMMENT: &str = "\
+Intended to be invoked using `call`, which executes the procedure in a fresh memory context.
+The lookup table is written to and read from that context only, leaving the caller's memory
+untouched. Initialization is not required.";
+
+const PERMUTE_ISOLATED_ARITHMETIC_COMMENT: &str = "\
+Intended to be invoked using `call`, which executes the procedure in a fresh memory context.
+The lookup table's entries are computed arithmetically, so no memory is written.";
+
+/// The Miden assembly source of the Tip5 permutation module.
+pub fn permutation_module() -> String {
+    permutation_module_with(&Options::default()).to_string()
+}
+
+/// The Tip5 permutation module as assembled by a [`Tip5Library`] with the given options.
+pub(crate) fn permutation_module_with(options: &Options) -> MasmModule {
+    let parameters = &options.parameters;
+    let mds_strategy = match parameters.state_size() {
+        STATE_SIZE => options.mds_strategy,
+        _ => MdsStrategy::Unrolled,
+    };
+    let mds = match mds_strategy {
+        MdsStrategy::Convolution => vec![mds_residues(parameters), mds()],
+        MdsStrategy::Unrolled => vec![mds_unrolled(parameters)],
+    };
+    let rounds = (0..parameters.num_rounds()).map(|r| round(parameters, r, mds_strategy));
+    MasmModule::new()
+        .with_constant("TABLE_BASE", options.table_base.into())
+        .with_commented_constant("SENTINEL", SENTINEL.into(), "'tip5' in ASCII")
+        .with_procedure(init(options.table_base))
+        .with_procedure(init_from_advice())
+        .with_procedure(ensure_init(options.table_base))
+        .with_procedure(assert_table_present(options.table_base))
+        .with_procedure(table_lookup(options.table_base))
+        .with_procedure(arithmetic_lookup())
+        .with_procedure(lookup(options))
+        .with_procedure(split_and_lookup(options))
+        .with_procedure(sbox_layer(parameters, "split_and_lookup"))
+        .with_procedures(mds)
+        .with_procedures(rounds)
+        .with_procedure(permute(parameters.num_rounds()))
+        .with_procedure(permute_isolated(options))
+}
+
+/// The Tip4-like permutation module as assembled by a [`Tip5Library`]. Its S-boxes
+/// invoke procedure `split_and_lookup` of the library's permutation module, sharing the lookup
+/// table.
+pub(crate) fn tip4_like_module() -> MasmModule {
+    let parameters = PermutationParameters::tip4_like();
+    let rounds = (0..parameters.num_rounds()).map(|r| round(&parameters, r, MdsStrategy::Unrolled));
+    MasmModule::new()
+        .with_import(&format!(
+            "{}::{}",
+            Tip5Library::NAMESPACE,
+            Tip5Library::PERMUTATION_MODULE
+        ))
+        .with_procedure(sbox_layer(&parameters, "permutation::split_and_lookup"))
+        .with_procedure(mds_unrolled(&parameters))
+        .with_procedures(rounds)
+        .with_procedure(permute(parameters.num_rounds()))
+}
+
+/// Writes the lookup table to the given base address, which is known when the module is generated
+/// and thus becomes part of every `mem_store` instruction.
+fn init(table_base: u32) -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    for (address, entry) in (table_base..).zip(LOOKUP_TABLE) {
+        body.aligned_line([Push(entry.into()), MemStoreImm(address)], 8);
+    }
+    let sentinel_address = table_base + Tip5Library::TABLE_SIZE;
+    body.line([PushConstant("SENTINEL"), MemStoreImm(sentinel_address)]);
+    MasmProcedure::new("init", body).exported()
+}
+
+fn init_from_advice() -> MasmProcedure {
+    let commitment = Rpo256::hash_elements(lookup_table_advice().stack());
+    let commitment = std::array::from_fn(|i| commitment.as_elements()[i].as_int());
+    let mut pipe = MasmBlock::new();
+    pipe.commented_line([AdvPipe], "_ ptr' capacity digest rate");
+
+    let mut body = MasmBlock::new();
+    body.commented_line([PadW, PadW, PadW], "_ 0 0 0 0 0 0 0 0 0 0 0 0")
+        .commented_line(
+            [PushConstant("TABLE_BASE"), MovDn(12)],
+            "_ ptr 0 0 0 0 0 0 0 0 0 0 0 0",
+        )
+        .repeat(LOOKUP_TABLE.len() / 2, pipe)
+        .commented_line([DropW], "_ ptr' capacity digest")
+        .line([PushWord(commitment)])
+        .commented_line([AssertEqW], "_ ptr' capacity")
+        .commented_line([DropW], "_ ptr'")
+        .line([PushConstant("SENTINEL"), Swap(1), MemStore]);
+    MasmProcedure::new("init_from_advice", body)
+        .exported()
+        .with_comment(INIT_FROM_ADVICE_COMMENT)
+}
+
+/// Pushes the current value of the sentinel following the table at the given base address.
+fn load_sentinel(table_base: u32) -> [Instruction; 1] {
+    [MemLoadImm(table_base + Tip5Library::TABLE_SIZE)]
+}
+
+fn ensure_init(table_base: u32) -> MasmProcedure {
+    let mut init = MasmBlock::new();
+    init.line([exec("init")]);
+    let mut body = MasmBlock::new();
+    body.line(load_sentinel(table_base))
+        .line([PushConstant("SENTINEL"), Neq])
+        .if_true(init);
+    MasmProcedure::new("ensure_init", body).exported()
+}
+
+fn assert_table_present(table_base: u32) -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.line(load_sentinel(table_base))
+        .line([PushConstant("SENTINEL"), AssertEq]);
+    MasmProcedure::new("assert_table_present", body).exported()
+}
+
+/// Looks up a byte in the table at the given base address. The base is added to the byte as an
+/// immediate value, or not at all if it is 0.
+fn table_lookup(table_base: u32) -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    if table_base != 0 {
+        body.line([AddImm(table_base.into())]);
+    }
+    body.commented_line([MemLoad], "_ byte'");
+    MasmProcedure::new("table_lookup", body)
+        .with_comment(TABLE_LOOKUP_COMMENT)
+        .with_stack_comment("_ byte")
+}
+
+fn arithmetic_lookup() -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.commented_line([AddImm(1), DupTop, DupTop, Mul, Mul], "_ (byte+1)^3")
+        .commented_line([U32UncheckedModImm(257), SubImm(1)], "_ byte'");
+    MasmProcedure::new("arithmetic_lookup", body)
+        .with_comment(ARITHMETIC_LOOKUP_COMMENT)
+        .with_stack_comment("_ byte")
+}
+
+fn lookup(options: &Options) -> MasmProcedure {
+    let lookup = match options.arithmetic_lookup {
+        true => "arithmetic_lookup",
+        false => "table_lookup",
+    };
+    let mut body = MasmBlock::new();
+    body.line([exec(lookup)]);
+    MasmProcedure::new("lookup", body)
+}
+
+fn split_and_lookup(options: &Options) -> MasmProcedure {
+    let lookup = || exec("lookup");
+    let mut body = MasmBlock::new();
+    if options.table_assertions && !options.arithmetic_lookup {
+        body.line([exec("assert_table_present")]);
+    }
+    body.commented_line([MulImm(u32::MAX.into())], "_ felt (un-montgomery'd)")
+        .commented_line([U32Split], "_ lo  hi")
+        .commented_line([U32UncheckedDivmodImm(1 << 16)], "_ lo  hi_hi  hi_lo")
+        .commented_line(
+            [U32UncheckedDivmodImm(1 << 8)],
+            "_ lo  hi_hi  hi_lo_hi  hi_lo_lo",
+        )
+        .commented_line([lookup()], "_ lo  hi_hi  hi_lo_hi  hi_lo_lo'")
+        .commented_line([Swap(1)], "_ lo  hi_hi  hi_lo_lo' hi_lo_hi")
+        .commented_line([lookup()], "_ lo  hi_hi  hi_lo_lo' hi_lo_hi'")
+        .commented_line([MulImm(1 << 8), Add], "_ lo  hi_hi  hi_lo'")
+        .commented_line([Swap(1)], "_ lo  hi_lo' hi_hi")
+        .commented_line(
+            [U32UncheckedDivmodImm(1 << 8)],
+            "_ lo  hi_lo' hi_hi_hi  hi_hi_lo",
+        )
+        .commented_line([lookup()], "_ lo  hi_lo' hi_hi_hi  hi_hi_lo'")
+        .commented_line([Swap(1)], "_ lo  hi_lo' hi_hi_lo' hi_hi_hi")
+        .commented_line([lookup()], "_ lo  hi_lo' hi_hi_lo' hi_hi_hi'")
+        .commented_line([MulImm(1 << 8), Add], "_ lo  hi_lo' hi_hi'")
+        .commented_line([MulImm(1 << 16), Add], "_ lo  hi'")
+        .commented_line([MulImm(1 << 32), Swap(1)], "_ hi' lo")
+        .commented_line([U32UncheckedDivmodImm(1 << 16)], "_ hi' lo_hi  lo_lo")
+        .commented_line(
+            [U32UncheckedDivmodImm(1 << 8)],
+            "_ hi' lo_hi  lo_lo_hi  lo_lo_lo",
+        )
+        .commented_line([lookup()], "_ hi' lo_hi  lo_lo_hi  lo_lo_lo'")
+        .commented_line([Swap(1)], "_ hi' lo_hi  lo_lo_lo' lo_lo_hi")
+        .commented_line([lookup()], "_ hi' lo_hi  lo_lo_lo' lo_lo_hi'")
+        .commented_line([MulImm(1 << 8), Add], "_ hi' lo_hi  lo_lo'")
+        .commented_line([Swap(1)], "_ hi' lo_lo' lo_hi")
+        .commented_line(
+            [U32UncheckedDivmodImm(1 << 8)],
+            "_ hi' lo_lo' lo_hi_hi  lo_hi_lo",
+        )
+        .commented_line([lookup()], "_ hi' lo_lo' lo_hi_hi  lo_hi_lo'")
+        .commented_line([Swap(1)], "_ hi' lo_lo' lo_hi_lo' lo_hi_hi")
+        .commented_line([lookup()], "_ hi' lo_lo' lo_hi_lo' lo_hi_hi'")
+        .commented_line([MulImm(1 << 8), Add], "_ hi' lo_lo' lo_hi'")
+        .commented_line([MulImm(1 << 16), Add], "_ hi' lo'")
+        .commented_line([Add], "_ felt'")
+        .commented_line([DivImm(u32::MAX.into())], "_ felt' (re-montgomery'd)");
+    MasmProcedure::new("split_and_lookup", body)
+        .exported()
+        .with_comment(SPLIT_AND_LOOKUP_COMMENT)
+}
+
+/// The S-box layer, invoking the given procedure for the split-and-lookup S-boxes.
+fn sbox_layer(parameters: &PermutationParameters, split_and_lookup: &str) -> MasmProcedure {
+    let bottom = parameters.state_size() - 1;
+    let mut body = MasmBlock::new();
+    for _ in 0..parameters.num_split_and_lookup() {
+        body.line([exec(split_and_lookup), bring_down(bottom)]);
+    }
+    for _ in parameters.num_split_and_lookup()..parameters.state_size() {
+        body.line([
+            DupTop,
+            DupTop,
+            Mul,
+            DupTop,
+            DupTop,
+            Mul,
+            Mul,
+            Mul,
+            bring_down(bottom),
+        ]);
+    }
+    MasmProcedure::new("sbox_layer", body)
+        .exported()
+        .with_comment(SBOX_LAYER_COMMENT)
+}
+
+/// A layer of butterflies, each turning the pair `(x, y)` into `(x + y, x - y)`. The layer
+/// processes the given groups of elements in order, pairing the `j`th element of a group with
+/// the `j`th element of the group's second half. Each group is given by its size, the factor
+/// applied to `y` before the butterfly, if any, and the factor applied to `x - y` after it, if
+/// any. Afterwards, each group's sums precede its differences.
+fn butterfly_layer(groups: &[(usize, Option<BFieldElement>, Option<BFieldElement>)]) -> MasmBlock {
+    let mut block = MasmBlock::new();
+    for &(size, pre_factor, post_factor) in groups {
+        let half = size / 2;
+        for j in 0..half {
+            let mut line = vec![bring_up(half - j)];
+            line.extend(pre_factor.map(|factor| MulImm(factor.value())));
+            line.extend([Dup(1), Dup(1), Add, MovDn(2), Sub]);
+            line.extend(post_factor.map(|factor| MulImm(factor.value())));
+            line.extend([MovDn(15), MovDn(14 - j)]);
+            block.line(line);
+        }
+    }
+    block
+}
+
+/// Brings the element at the given position to the top of the stack. Since `movup.1` is not a
+/// valid instruction, `swap.1` is used for position 1.
+fn bring_up(position: usize) -> Instruction {
+    match position {
+        1 => Swap(1),
+        _ => MovUp(position),
+    }
+}
+
+/// Moves the top of the stack to the given position. Since `movdn.1` is not a valid instruction,
+/// `swap.1` is used for position 1.
+fn bring_down(position: usize) -> Instruction {
+    match position {
+        1 => Swap(1),
+        _ => MovDn(position),
+    }
+}
+
+/// The residue of the polynomial with the given coefficients modulo `x^(n/2) - lambda`, where `n`
+/// is the number of coefficients.
+fn residue(coefficients: &[BFieldElement], lambda: BFieldElement) -> Vec<BFieldElement> {
+    let (low, high) = coefficients.split_at(coefficients.len() / 2);
+    low.iter()
+        .zip(high)
+        .map(|(&l, &h)| l + lambda * h)
+        .collect()
+}
+
+/// The product of the top word on the stack with the given residue of the MDS matrix's first
+/// column modulo `x^4 - lambda`, scaled by 1/4. The result is moved to the bottom of the state.
+fn block_product(lambda: BFieldElement, residue: &[BFieldElement]) -> MasmBlock {
+    let scale = BFieldElement::new(4).inverse();
+    let entry = |k: usize, j: usize| match j <= k {
+        true => scale * residue[k - j],
+        false => scale * lambda * residue[4 + k - j],
+    };
+
+    let mut block = MasmBlock::new();
+    for (num_done, k) in (0..4).rev().enumerate() {
+        let products = (0..4).map(|j| {
+            let position = if j == 0 { num_done } else { num_done + 1 + j };
+            let product = [Dup(position), MulImm(entry(k, j).value())];
+            let sum = (j > 0).then_some(Add);
+            product.into_iter().chain(sum)
+        });
+        let products = products.collect::<Vec<_>>();
+        block.line(products[..2].iter().cloned().flatten());
+        block.line(products[2..].iter().cloned().flatten());
+    }
+    block.line([SwapW(1), DropW, MovDnW(3)]);
+    block
+}
+
+fn mds_residues(parameters: &PermutationParameters) -> MasmProcedure {
+    let one = BFieldElement::new(1);
+    let i = SQRT_MINUS_ONE;
+    let column = parameters.mds_first_column();
+    let column_plus = residue(column, one);
+    let column_minus = residue(column, -one);
+    let blocks = [
+        ("x^4 - 1", one, residue(&column_plus, one)),
+        ("x^4 + 1", -one, residue(&column_plus, -one)),
+        ("x^4 - i", i, residue(&column_minus, i)),
+        ("x^4 + i", -i, residue(&column_minus, -i)),
+    ];
+
+    let mut body = MasmBlock::new();
+    body.comment("reduce modulo x^8 - 1 and x^8 + 1")
+        .append(butterfly_layer(&[(16, None, None)]))
+        .blank_line()
+        .comment("reduce modulo x^4 - 1, x^4 + 1, x^4 - i, and x^4 + i")
+        .append(butterfly_layer(&[(8, None, None), (8, Some(i), None)]));
+    for (factor, lambda, residue) in blocks {
+        body.blank_line()
+            .comment(&format!("multiply modulo {factor}"))
+            .append(block_product(lambda, &residue));
+    }
+    body.blank_line()
+        .comment("reconstruct modulo x^8 - 1 and x^8 + 1")
+        .append(butterfly_layer(&[(8, None, None), (8, None, Some(-i))]));
+    MasmProcedure::new("mds_residues", body).with_comment(MDS_COMMENT)
+}
+
+fn mds() -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.line([exec("mds_residues")])
+        .blank_line()
+        .comment("reconstruct modulo x^16 - 1")
+        .append(butterfly_layer(&[(STATE_SIZE, None, None)]));
+    MasmProcedure::new("mds", body).exported()
+}
+
+fn mds_unrolled(parameters: &PermutationParameters) -> MasmProcedure {
+    let size = parameters.state_size();
+    let column = parameters.mds_first_column();
+    let mut body = MasmBlock::new();
+    for i in 0..size {
+        let products = (0..size).flat_map(|j| {
+            let entry = column[(i + 1 + j) % size];
+            [Dup(size - 1), MulImm(entry.value())]
+        });
+        let sums = vec![Add; size - 1];
+        body.line(products.chain(sums).chain([LocStore(i as u16)]));
+    }
+    let last = size as u16 - 1;
+    body.line([
+        bring_up(size - 1),
+        LocLoad(last),
+        Swap(1),
+        Drop,
+        bring_down(size - 1),
+    ]);
+    for i in 0..last {
+        body.line([LocLoad(i), Swap(i as usize + 1), Drop]);
+    }
+    MasmProcedure::new("mds", body)
+        .exported()
+        .with_locals(size as u16)
+        .with_comment(MDS_UNROLLED_COMMENT)
+}
+
+/// One round of the permutation. Using the convolution strategy, the addition of the round's
+/// constants is fused into the last layer of butterflies of procedure `mds`.
+fn round(
+    parameters: &PermutationParameters,
+    round: usize,
+    mds_strategy: MdsStrategy,
+) -> MasmProcedure {
+    let constants = parameters.round_constants(round);
+    let mut body = MasmBlock::new();
+    body.line([exec("sbox_layer")]);
+    match mds_strategy {
+        MdsStrategy::Convolution => {
+            body.line([exec("mds_residues")])
+                .blank_line()
+                .comment("reconstruct modulo x^16 - 1 and add round constants");
+            let half = STATE_SIZE / 2;
+            for j in 0..half {
+                let (low, high) = (constants[j], constants[j + half]);
+                body.line([bring_up(half - j), Dup(1), Dup(1), Add, MovDn(2), Sub]);
+                body.line([
+                    AddImm(high.value()),
+                    MovDn(15),
+                    AddImm(low.value()),
+                    MovDn(14 - j),
+                ]);
+            }
+        }
+        MdsStrategy::Unrolled => {
+            body.line([exec("mds")])
+                .blank_line()
+                .comment("add round constants");
+            let bottom = bring_down(parameters.state_size() - 1);
+            for constant in constants {
+                body.line([AddImm(constant.value()), bottom.clone()]);
+            }
+        }
+    }
+    MasmProcedure::new(format!("round_{round}"), body)
+}
+
+fn permute(num_rounds: usize) -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    for round in 0..num_rounds {
+        body.line([exec(format!("round_{round}"))]);
+    }
+    MasmProcedure::new("permute", body).exported()
+}
+
+fn permute_isolated(options: &Options) -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    let comment = match options.arithmetic_lookup {
+        true => PERMUTE_ISOLATED_ARITHMETIC_COMMENT,
+        false => {
+            body.line([exec("init")]);
+            PERMUTE_ISOLATED_COMMENT
+        }
+    };
+    body.line([exec("permute")]);
+    MasmProcedure::new("permute_isolated", body)
+        .exported()
+        .with_comment(comment)
+}
+
+fn exec(procedure: impl Into<String>) -> Instruction {
+    Instruction::exec(procedure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TIP5_LIB;
+
+    #[test]
+    fn checked_in_permutation_module_is_up_to_date() {
+        assert!(
+            TIP5_LIB == permutation_module(),
+            "src/masm/permutation.masm is outdated, regenerate it using \
+            `cargo run --example generate_masm`"
+        );
+    }
+
+    #[test]
+    fn permute_isolated_initializes_lookup_table_only_if_it_is_read() {
+        for arithmetic_lookup in [false, true] {
+            let options = Options {
+                arithmetic_lookup,
+                ..Options::default()
+            };
+            let module = permutation_module_with(&options);
+            let procedures = module.procedures().iter();
+            let mut permute_isolated = procedures.filter(|p| p.name() == "permute_isolated");
+            let source = permute_isolated.next().unwrap().to_string();
+            assert_eq!(!arithmetic_lookup, source.contains("exec.init"));
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Generation of the Miden assembly sources of the sponge and hash modules built on top of the
+//! permutation, [`TIP5_SPONGE`](crate::TIP5_SPONGE), [`TIP5_HASH`](crate::TIP5_HASH), and
+//! [`TIP4_LIKE_HASH`](crate::TIP4_LIKE_HASH).
+//!
+//! The generated sources are checked in next to `src/masm/permutation.masm`. Regenerate them
+//! using `cargo run --example generate_masm`.
+
+use crate::builder::Instruction;
+use crate::builder::Instruction::*;
+use crate::builder::MasmBlock;
+use crate::builder::MasmModule;
+use crate::builder::MasmProcedure;
+use crate::Tip5Library;
+
+/// The dimensions of a sponge construction.
+struct Sponge {
+    state_size: usize,
+    rate: usize,
+    digest_length: usize,
+}
+
+/// The sponge of Tip5, compatible with [`Tip5State`](twenty_first::shared_math::tip5::Tip5State).
+const TIP5_SPONGE: Sponge = Sponge {
+    state_size: 16,
+    rate: 10,
+    digest_length: 5,
+};
+
+/// The sponge of the Tip4-like hash functions.
+const TIP4_LIKE_SPONGE: Sponge = Sponge {
+    state_size: 12,
+    rate: 8,
+    digest_length: 4,
+};
+
+impl Sponge {
+    fn capacity(&self) -> usize {
+        self.state_size - self.rate
+    }
+
+    /// The stack comment for a state on top of the stack.
+    fn state(&self) -> String {
+        format!("_ s{} .. s0", self.state_size - 1)
+    }
+
+    /// The stack comment for a state on top of the stack followed by a chunk of input.
+    fn state_and_chunk(&self) -> String {
+        format!("{} c{} .. c0", self.state(), self.rate - 1)
+    }
+
+    /// The stack comment for a state after the permutation, with the digest on top.
+    fn state_and_digest(&self) -> String {
+        let digest_length = self.digest_length;
+        let last = self.state_size - 1;
+        let last_digest = digest_length - 1;
+        format!("_ s{last} .. s{digest_length} d{last_digest} .. d0")
+    }
+
+    /// The stack comment for a digest on top of the stack.
+    fn digest(&self) -> String {
+        format!("_ d{} .. d0", self.digest_length - 1)
+    }
+}
+
+/// The Miden assembly source of the Tip5 sponge module.
+pub fn sponge_module() -> String {
+    let permutation = Tip5Library::PERMUTATION_MODULE;
+    MasmModule::new()
+        .with_import(&import(permutation))
+        .with_procedure(init_fixed_length())
+        .with_procedure(init_variable_length())
+        .with_procedure(absorb(&TIP5_SPONGE, &format!("{permutation}::permute")).exported())
+        .with_procedure(squeeze())
+        .to_string()
+}
+
+/// The Miden assembly source of the Tip5 hash module.
+pub fn hash_module() -> String {
+    let permutation = Tip5Library::PERMUTATION_MODULE;
+    let sponge = Tip5Library::SPONGE_MODULE;
+    let mut hash_pair = MasmBlock::new();
+    hash_pair.line([exec("hash_10")]);
+    let hash_pair = MasmProcedure::new("hash_pair", hash_pair)
+        .exported()
+        .with_comment("Hashes the two digests on top of the stack, the left one being the topmost.")
+        .with_stack_comment("_ r4 .. r0 l4 .. l0");
+    let init = vec![exec(format!("{sponge}::init_variable_length"))];
+    MasmModule::new()
+        .with_import(&import(permutation))
+        .with_import(&import(sponge))
+        .with_procedure(load_chunk(TIP5_SPONGE.rate))
+        .with_procedure(load_padded_chunk(TIP5_SPONGE.rate))
+        .with_procedure(hash_fixed_length(
+            &TIP5_SPONGE,
+            "hash_10",
+            "Hashes the 10 elements on top of the stack, leaving only the 5-element digest.",
+            &format!("{permutation}::permute"),
+        ))
+        .with_procedure(hash_pair)
+        .with_procedure(hash_varlen(
+            &TIP5_SPONGE,
+            init,
+            &format!("{sponge}::absorb"),
+        ))
+        .to_string()
+}
+
+/// The Miden assembly source of the Tip4-like hash module.
+pub fn tip4_like_hash_module() -> String {
+    let permute = format!("{}::permute", Tip5Library::TIP4_LIKE_MODULE);
+    let init = vec![PadW; TIP4_LIKE_SPONGE.state_size / 4];
+    MasmModule::new()
+        .with_import(&import(Tip5Library::TIP4_LIKE_MODULE))
+        .with_procedure(absorb(&TIP4_LIKE_SPONGE, &permute))
+        .with_procedure(load_chunk(TIP4_LIKE_SPONGE.rate))
+        .with_procedure(load_padded_chunk(TIP4_LIKE_SPONGE.rate))
+        .with_procedure(hash_fixed_length(
+            &TIP4_LIKE_SPONGE,
+            "hash_pair",
+            "Hashes the two digests on top of the stack, the left one being the topmost, leaving \
+            only\nthe 4-element digest. The capacity is set to all ones for hashing input of \
+            fixed length.",
+            &permute,
+        ))
+        .with_procedure(hash_varlen(&TIP4_LIKE_SPONGE, init, "absorb"))
+        .to_string()
+}
+
+fn import(module: &str) -> String {
+    format!("{}::{module}", Tip5Library::NAMESPACE)
+}
+
+fn init_fixed_length() -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.line([PushWord([1; 4]), Push(1), Push(1)])
+        .line([PadW, PadW])
+        .line([Push(0), Push(0)]);
+    MasmProcedure::new("init_fixed_length", body)
+        .exported()
+        .with_comment(
+            "Pushes a sponge state for hashing input of fixed length: the capacity is set to all \
+            ones.",
+        )
+}
+
+fn init_variable_length() -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.line(vec![PadW; 4]);
+    MasmProcedure::new("init_variable_length", body)
+        .exported()
+        .with_comment(
+            "Pushes a sponge state for hashing input of variable length: the capacity is set to \
+            all\nzeros.",
+        )
+}
+
+/// Adds a chunk to the rate of the state, then applies the given permutation.
+fn absorb(sponge: &Sponge, permute: &str) -> MasmProcedure {
+    let rate = sponge.rate;
+    let mut add = MasmBlock::new();
+    add.line([MovUp(rate), Instruction::Add, MovDn(rate - 1)]);
+    let mut body = MasmBlock::new();
+    body.repeat(rate, add).line([exec(permute)]);
+    MasmProcedure::new("absorb", body)
+        .with_comment(&format!(
+            "Adds the {rate}-element chunk on top of the stack to the rate of the state below \
+            it, then\napplies the permutation."
+        ))
+        .with_stack_comment(&sponge.state_and_chunk())
+}
+
+fn squeeze() -> MasmProcedure {
+    let mut body = MasmBlock::new();
+    body.line([LocStoreW(0), SwapW(1)])
+        .line([LocStoreW(1), SwapW(2)])
+        .line([LocStoreW(2), SwapW(2), SwapW(1)])
+        .commented_line([exec("permutation::permute")], "_ s15' .. s0'")
+        .line([PadW, LocLoadW(2)])
+        .commented_line([MovUp(2), Drop, MovUp(2), Drop], "_ s15' .. s0' s9 s8")
+        .line([PadW, LocLoadW(1)])
+        .commented_line([PadW, LocLoadW(0)], "_ s15' .. s0' s9 .. s0");
+    MasmProcedure::new("squeeze", body)
+        .exported()
+        .with_locals(3)
+        .with_comment(
+            "Pushes the rate of the state on top of the stack, then applies the permutation to \
+            the\nstate below it.",
+        )
+        .with_stack_comment(&TIP5_SPONGE.state())
+}
+
+/// Pushes the `rate` elements stored at consecutive addresses.
+fn load_chunk(rate: usize) -> MasmProcedure {
+    let mut load = MasmBlock::new();
+    load.line([DupTop, MemLoad, Swap(1), SubImm(1)]);
+    let mut body = MasmBlock::new();
+    body.line([AddImm(rate as u64 - 1)])
+        .repeat(rate, load)
+        .commented_line([Drop], &format!("_ c{} .. c0", rate - 1));
+    MasmProcedure::new("load_chunk", body)
+        .with_comment(&format!(
+            "Pushes the {rate} elements stored at addresses ptr .. ptr+{}.",
+            rate - 1
+        ))
+        .with_stack_comment("_ ptr")
+}
+
+/// Pushes the fewer than `rate` elements stored at consecutive addresses, padded to `rate`
+/// elements.
+fn load_padded_chunk(rate: usize) -> MasmProcedure {
+    let mut load = MasmBlock::new();
+    load.line([Dup(1), Dup(1), Instruction::Add, MemLoad]);
+    let mut pad = MasmBlock::new();
+    pad.line([Dup(2), Dup(1), Eq]);
+    let mut element = MasmBlock::new();
+    element
+        .line([Dup(2), Dup(1), U32UncheckedGt])
+        .if_else(load, pad)
+        .commented_line([MovDn(3), SubImm(1)], "_ c_i n ptr i-1");
+    let mut body = MasmBlock::new();
+    body.commented_line([Push(rate as u64 - 1)], "_ n ptr i")
+        .repeat(rate, element)
+        .commented_line([Drop, Drop, Drop], &format!("_ c{} .. c0", rate - 1));
+    MasmProcedure::new("load_padded_chunk", body)
+        .with_comment(&format!(
+            "Pushes the {rate} elements stored at addresses ptr .. ptr+n-1, padded with a 1 \
+            followed by 0s.\nRequires n < {rate}."
+        ))
+        .with_stack_comment("_ n ptr")
+}
+
+/// Drops all elements of the state but the digest on top of it.
+fn keep_digest(sponge: &Sponge) -> MasmBlock {
+    let mut body = MasmBlock::new();
+    match (sponge.state_size, sponge.digest_length) {
+        (16, 5) => body
+            .line([SwapW(2), DropW, SwapW(2), DropW])
+            .line([MovUp(5), Drop])
+            .line([MovUp(5), Drop])
+            .commented_line([MovUp(5), Drop], &sponge.digest()),
+        (12, 4) => body.commented_line([SwapW(2), DropW, DropW], &sponge.digest()),
+        _ => unreachable!("only the sponges of Tip5 and the Tip4-like permutation are supported"),
+    };
+    body
+}
+
+/// Hashes the `rate` elements on top of the stack using the given permutation.
+fn hash_fixed_length(sponge: &Sponge, name: &str, comment: &str, permute: &str) -> MasmProcedure {
+    let mut prepend_one = MasmBlock::new();
+    prepend_one.line([Push(1), MovDn(sponge.rate)]);
+    let mut body = MasmBlock::new();
+    body.repeat(sponge.capacity(), prepend_one)
+        .commented_line([exec(permute)], &sponge.state_and_digest())
+        .append(keep_digest(sponge));
+    MasmProcedure::new(name, body)
+        .exported()
+        .with_comment(comment)
+        .with_stack_comment(&format!("_ x{} .. x0", sponge.rate - 1))
+}
+
+/// Hashes input of variable length stored in memory, starting from the state pushed by `init`
+/// and absorbing chunks using the given procedure.
+fn hash_varlen(sponge: &Sponge, init: Vec<Instruction>, absorb: &str) -> MasmProcedure {
+    let rate = sponge.rate as u64;
+    let mut absorb_chunk = MasmBlock::new();
+    absorb_chunk
+        .line([LocLoad(0), exec("load_chunk")])
+        .line([exec(absorb)])
+        .line([LocLoad(0), AddImm(rate), LocStore(0)])
+        .line([LocLoad(2), SubImm(1), DupTop, LocStore(2), NeqImm(0)]);
+    let mut body = MasmBlock::new();
+    body.line([LocStore(0)])
+        .commented_line(
+            [U32CheckedDivmodImm(rate as u32)],
+            "_ num_chunks num_remaining",
+        )
+        .line([LocStore(1), LocStore(2)])
+        .commented_line(init, &sponge.state())
+        .line([LocLoad(2), NeqImm(0)])
+        .while_true(absorb_chunk)
+        .line([LocLoad(1), LocLoad(0)])
+        .commented_line([exec("load_padded_chunk")], &sponge.state_and_chunk())
+        .commented_line([exec(absorb)], &sponge.state_and_digest())
+        .append(keep_digest(sponge));
+    MasmProcedure::new("hash_varlen", body)
+        .exported()
+        .with_locals(3)
+        .with_comment(&format!(
+            "Hashes the len elements stored at addresses ptr .. ptr+len-1, leaving only the \
+            {}-element\ndigest. The input is padded with a 1 followed by 0s to the next \
+            multiple of the rate.",
+            sponge.digest_length
+        ))
+        .with_stack_comment("_ len ptr")
+}
+
+fn exec(procedure: impl Into<String>) -> Instruction {
+    Instruction::exec(procedure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TIP4_LIKE_HASH;
+    use crate::TIP5_HASH;
+    use crate::TIP5_SPONGE;
+
+    #[test]
+    fn checked_in_hash_modules_are_up_to_date() {
+        let modules = [
+            ("sponge", TIP5_SPONGE, sponge_module()),
+            ("hash", TIP5_HASH, hash_module()),
+            ("tip4_like_hash", TIP4_LIKE_HASH, tip4_like_hash_module()),
+        ];
+        for (name, checked_in, generated) in modules {
+            assert!(
+                checked_in == generated,
+                "src/masm/{name}.masm is outdated, regenerate it using \
+                `cargo run --example generate_masm`"
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Contains Miden assembly of the [Tip5 permutation](https://eprint.iacr.org/2023/107.pdf),
+//! packaged as a Miden library, as well as helpers for executing and proving the permutation.
+//!
+//! On request, the library also contains hash functions built from a permutation shaped like Tip4',
+//! the 12-element variant of Tip5. They are not compatible with Tip4', see
+//! [`Tip5Library::with_tip4_like`].
+
+mod builder;
+mod bundle;
+#[cfg(test)]
+mod consistency_tests;
+#[cfg(test)]
+mod differential_tests;
+mod generator;
+mod hash_generator;
+mod library;
+mod masm;
+mod parameters;
+mod proof;
+mod reference;
+#[cfg(test)]
+mod test_helpers;
+mod vm;
+
+pub use builder::Instruction;
+pub use builder::MasmBlock;
+pub use builder::MasmModule;
+pub use builder::MasmProcedure;
+pub use bundle::BundleError;
+pub use bundle::ProofBundle;
+pub use generator::permutation_module;
+pub use hash_generator::hash_module;
+pub use hash_generator::sponge_module;
+pub use hash_generator::tip4_like_hash_module;
+pub use library::LibraryError;
+pub use library::MdsStrategy;
+pub use library::Tip5Library;
+pub use masm::TIP4_LIKE_HASH;
+pub use masm::TIP5_HASH;
+pub use masm::TIP5_LIB;
+pub use masm::TIP5_PROGRAM;
+pub use masm::TIP5_SPONGE;
+pub use parameters::PermutationParameters;
+pub use proof::prove_permutation;
+pub use proof::prove_permutation_with;
+pub use proof::PermutationProof;
+pub use proof::ProofError;
+pub use proof::TIP5_PROGRAM_HASH;
+pub use reference::reference_permutation;
+pub use reference::reference_permutation_with;
+pub use reference::reference_trace;
+pub use reference::reference_trace_with;
+pub use reference::round_constants;
+pub use reference::tip4_like_hash_pair;
+pub use reference::tip4_like_hash_varlen;
+pub use reference::MAX_NUM_ROUNDS;
+pub use reference::TIP4_LIKE_DIGEST_LENGTH;
+pub use reference::TIP4_LIKE_RATE;
+pub use vm::assembler;
+pub use vm::assembler_with;
+pub use vm::hash_10;
+pub use vm::hash_varlen;
+pub use vm::lookup_table_advice;
+pub use vm::permute;
+pub use vm::stack_inputs;
+pub use vm::stack_outputs;
+pub use vm::state_from_outputs;
+pub use vm::tip5_program;
+pub use vm::tip5_program_with;
+pub use vm::State;
@@ -0,0 +1,479 @@
+use std::error::Error;
+use std::fmt;
+
+use miden_assembly::Library;
+use miden_assembly::LibraryNamespace;
+use miden_assembly::MaslLibrary;
+use miden_assembly::Module;
+use miden_assembly::ModulePath;
+use miden_assembly::Version;
+
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+use crate::generator::permutation_module_with;
+use crate::generator::tip4_like_module;
+use crate::hash_module;
+use crate::sponge_module;
+use crate::tip4_like_hash_module;
+use crate::PermutationParameters;
+
+/// The [Tip5](https://eprint.iacr.org/2023/107.pdf) permutation as a Miden library.
+///
+/// The library's root namespace is `tip5`. It contains the following modules:
+/// - `permutation`, which exports procedures `init`, `init_from_advice`, `ensure_init`,
+///   `assert_table_present`, `split_and_lookup`, `permute`, `permute_isolated`, `sbox_layer`,
+///   and `mds`. See [`TIP5_LIB`] for the source.
+/// - `sponge`, which exports procedures `init_fixed_length`, `init_variable_length`, `absorb`,
+///   and `squeeze`. See [`TIP5_SPONGE`] for the source.
+/// - `hash`, which exports procedures `hash_10`, `hash_pair`, and `hash_varlen`. See
+///   [`TIP5_HASH`] for the source.
+/// - `tip4_like` and `tip4_like_hash`, only if requested using [`Self::with_tip4_like`].
+///
+/// By default, the lookup table of the permutation is written to memory addresses 0 through 255,
+/// followed by a sentinel marking the table as initialized at address 256. Use
+/// [`Self::with_table_base`] to move both elsewhere.
+///
+/// The permutation module is generated according to the chosen options, so its source differs
+/// from [`TIP5_LIB`] unless all options are left at their defaults. Using
+/// [`Self::with_parameters`], it implements a different member of Tip5's family of permutations.
+///
+/// [`TIP5_LIB`]: crate::TIP5_LIB
+/// [`TIP5_SPONGE`]: crate::TIP5_SPONGE
+/// [`TIP5_HASH`]: crate::TIP5_HASH
+pub struct Tip5Library {
+    options: Options,
+    contents: MaslLibrary,
+}
+
+/// The choices made when assembling a [`Tip5Library`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Options {
+    pub(crate) table_base: u32,
+    pub(crate) table_assertions: bool,
+    pub(crate) arithmetic_lookup: bool,
+    pub(crate) mds_strategy: MdsStrategy,
+    pub(crate) parameters: PermutationParameters,
+    pub(crate) tip4_like: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            table_base: Tip5Library::DEFAULT_TABLE_BASE,
+            table_assertions: false,
+            arithmetic_lookup: false,
+            mds_strategy: MdsStrategy::Convolution,
+            parameters: PermutationParameters::tip5(),
+            tip4_like: false,
+        }
+    }
+}
+
+/// The way procedure `mds` of the permutation module multiplies the state with the MDS matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdsStrategy {
+    /// Computes the cyclic convolution of the state and the matrix's first column modulo the
+    /// factors of x^16 - 1. This is the default, being considerably cheaper. Only available for a
+    /// state size of 16, other state sizes use [`Self::Unrolled`] instead.
+    Convolution,
+
+    /// Computes every row's inner product with the state separately, storing the results in the
+    /// procedure's locals.
+    Unrolled,
+}
+
+impl Tip5Library {
+    /// The root namespace of the library.
+    pub const NAMESPACE: &str = "tip5";
+
+    /// The path of the permutation module relative to the library's root namespace.
+    pub const PERMUTATION_MODULE: &str = "permutation";
+
+    /// The path of the sponge module relative to the library's root namespace.
+    pub const SPONGE_MODULE: &str = "sponge";
+
+    /// The path of the hash module relative to the library's root namespace.
+    pub const HASH_MODULE: &str = "hash";
+
+    /// The path of the Tip4-like permutation module relative to the library's root namespace,
+    /// see [`Self::with_tip4_like`].
+    pub const TIP4_LIKE_MODULE: &str = "tip4_like";
+
+    /// The path of the Tip4-like hash module relative to the library's root namespace, see
+    /// [`Self::with_tip4_like`].
+    pub const TIP4_LIKE_HASH_MODULE: &str = "tip4_like_hash";
+
+    /// The memory address of the lookup table's first entry unless configured otherwise.
+    pub const DEFAULT_TABLE_BASE: u32 = 0;
+
+    /// The number of memory addresses occupied by the lookup table, not counting the sentinel.
+    pub const TABLE_SIZE: u32 = 256;
+
+    /// Places the lookup table at memory addresses `table_base` through `table_base + 255`, and
+    /// its sentinel at address `table_base + 256`.
+    ///
+    /// # Panics
+    /// Panics if the lookup table and its sentinel do not fit into the address space, see
+    /// [`Self::try_with_table_base`].
+    pub fn with_table_base(self, table_base: u32) -> Self {
+        self.try_with_table_base(table_base)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Self::with_table_base`], but fails instead of panicking if the lookup table and its
+    /// sentinel do not fit into the address space.
+    pub fn try_with_table_base(self, table_base: u32) -> Result<Self, LibraryError> {
+        if table_base.checked_add(Self::TABLE_SIZE).is_none() {
+            return Err(LibraryError::TableOutOfAddressSpace { table_base });
+        }
+        Ok(Self::new(Options {
+            table_base,
+            ..self.options
+        }))
+    }
+
+    /// Asserts that the lookup table has been initialized before every lookup if
+    /// `table_assertions` is set. This makes the permutation considerably more expensive and is
+    /// meant for debugging. Has no effect in combination with [`Self::with_arithmetic_lookup`].
+    pub fn with_table_assertions(self, table_assertions: bool) -> Self {
+        Self::new(Options {
+            table_assertions,
+            ..self.options
+        })
+    }
+
+    /// Computes the lookup table's entries arithmetically if `arithmetic_lookup` is set, instead
+    /// of reading them from memory. The permutation then neither accesses memory nor requires
+    /// initialization, at the cost of more cycles per lookup.
+    pub fn with_arithmetic_lookup(self, arithmetic_lookup: bool) -> Self {
+        Self::new(Options {
+            arithmetic_lookup,
+            ..self.options
+        })
+    }
+
+    /// Multiplies the state with the MDS matrix using the given strategy.
+    pub fn with_mds_strategy(self, mds_strategy: MdsStrategy) -> Self {
+        Self::new(Options {
+            mds_strategy,
+            ..self.options
+        })
+    }
+
+    /// Makes procedure `permute` apply the given number of rounds instead of Tip5's five. Rounds
+    /// beyond the fifth use round constants derived like Tip5's, see
+    /// [`round_constants`](crate::round_constants). Since the sponge and hash modules build on
+    /// `permute`, they change accordingly. Meant for experiments with reduced-round and
+    /// extended-round variants, which [`reference_permutation`](crate::reference_permutation)
+    /// implements in Rust.
+    ///
+    /// # Panics
+    /// Panics if `num_rounds` is 0 or exceeds
+    /// [`PermutationParameters::max_num_rounds`], see [`Self::try_with_num_rounds`].
+    pub fn with_num_rounds(self, num_rounds: usize) -> Self {
+        self.try_with_num_rounds(num_rounds)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Self::with_num_rounds`], but fails instead of panicking if `num_rounds` is 0 or
+    /// exceeds [`PermutationParameters::max_num_rounds`].
+    pub fn try_with_num_rounds(self, num_rounds: usize) -> Result<Self, LibraryError> {
+        let max_num_rounds = self.options.parameters.max_num_rounds();
+        if !(1..=max_num_rounds).contains(&num_rounds) {
+            return Err(LibraryError::InvalidNumRounds {
+                num_rounds,
+                max_num_rounds,
+            });
+        }
+        let parameters = self.options.parameters.with_num_rounds(num_rounds);
+        Ok(Self::new(Options {
+            parameters,
+            ..self.options
+        }))
+    }
+
+    /// Makes module `permutation` implement the permutation with the given parameters instead of
+    /// Tip5, including its number of rounds. Meant for evaluating the cost of alternative
+    /// parameters, which [`reference_permutation_with`](crate::reference_permutation_with)
+    /// implements in Rust. The sponge and hash modules assume a state size of 16 and are omitted
+    /// for other state sizes.
+    pub fn with_parameters(self, parameters: PermutationParameters) -> Self {
+        Self::new(Options {
+            parameters,
+            ..self.options
+        })
+    }
+
+    /// Adds the modules `tip4_like` and `tip4_like_hash` if `tip4_like` is set. Module
+    /// `tip4_like` exports procedures `sbox_layer`, `mds`, and `permute` of the permutation with
+    /// [`PermutationParameters::tip4_like`], whose S-boxes use procedure `split_and_lookup` of
+    /// module `permutation`, including its lookup table. Module `tip4_like_hash` exports
+    /// procedures `hash_pair` and `hash_varlen` built from it, see [`TIP4_LIKE_HASH`] for the
+    /// source.
+    ///
+    /// The permutation is *not* Tip4' and no known-answer tests of Tip4' back it. It is meant for
+    /// estimating the cost of a 12-element variant of Tip5 only.
+    ///
+    /// [`TIP4_LIKE_HASH`]: crate::TIP4_LIKE_HASH
+    pub fn with_tip4_like(self, tip4_like: bool) -> Self {
+        Self::new(Options {
+            tip4_like,
+            ..self.options
+        })
+    }
+
+    /// The memory address of the lookup table's first entry.
+    pub fn table_base(&self) -> u32 {
+        self.options.table_base
+    }
+
+    /// The memory address of the sentinel marking the lookup table as initialized.
+    pub fn sentinel_address(&self) -> u32 {
+        self.options.table_base + Self::TABLE_SIZE
+    }
+
+    /// The number of rounds applied by procedure `permute`.
+    pub fn num_rounds(&self) -> usize {
+        self.options.parameters.num_rounds()
+    }
+
+    /// The parameters of the permutation implemented by module `permutation`.
+    pub fn parameters(&self) -> &PermutationParameters {
+        &self.options.parameters
+    }
+
+    /// The source of every module of the library, paired with the module's path relative to the
+    /// library's root namespace, in the order the modules are listed by [`Library::modules`].
+    pub fn sources(&self) -> Vec<(&'static str, String)> {
+        Self::module_sources(&self.options)
+    }
+
+    fn new(options: Options) -> Self {
+        let namespace = LibraryNamespace::try_from(Self::NAMESPACE.to_string())
+            .expect("namespace must be valid");
+        let modules = Self::module_sources(&options)
+            .into_iter()
+            .map(|(path, source)| Self::module(&namespace, path, &source))
+            .collect();
+        let contents = MaslLibrary {
+            namespace,
+            version: Version::default(),
+            modules,
+        };
+        Self { options, contents }
+    }
+
+    fn module_sources(options: &Options) -> Vec<(&'static str, String)> {
+        let mut sources = vec![(
+            Self::PERMUTATION_MODULE,
+            permutation_module_with(options).to_string(),
+        )];
+        if options.parameters.state_size() == STATE_SIZE {
+            sources.push((Self::SPONGE_MODULE, sponge_module()));
+            sources.push((Self::HASH_MODULE, hash_module()));
+        }
+        if options.tip4_like {
+            sources.push((Self::TIP4_LIKE_MODULE, tip4_like_module().to_string()));
+            sources.push((Self::TIP4_LIKE_HASH_MODULE, tip4_like_hash_module()));
+        }
+        sources
+    }
+
+    fn module(namespace: &LibraryNamespace, path: &str, source: &str) -> Module {
+        let path = ModulePath::try_from(path.to_string())
+            .expect("module path must be valid")
+            .to_absolute(namespace);
+        let ast = miden_assembly::parse_module(source).expect("module source must parse");
+        Module::new(path, ast)
+    }
+}
+
+impl Default for Tip5Library {
+    fn default() -> Self {
+        Self::new(Options::default())
+    }
+}
+
+impl Library for Tip5Library {
+    type ModuleIterator<'a> = <MaslLibrary as Library>::ModuleIterator<'a>;
+
+    fn root_ns(&self) -> &LibraryNamespace {
+        self.contents.root_ns()
+    }
+
+    fn version(&self) -> &Version {
+        self.contents.version()
+    }
+
+    fn modules(&self) -> Self::ModuleIterator<'_> {
+        self.contents.modules()
+    }
+}
+
+/// The reasons a [`Tip5Library`] cannot be assembled with the chosen options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibraryError {
+    /// The lookup table and its sentinel do not fit into the address space.
+    TableOutOfAddressSpace { table_base: u32 },
+
+    /// The number of rounds is 0 or exceeds the permutation's maximum.
+    InvalidNumRounds {
+        num_rounds: usize,
+        max_num_rounds: usize,
+    },
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TableOutOfAddressSpace { table_base } => write!(
+                f,
+                "lookup table must fit into the address space, but starts at address {table_base}"
+            ),
+            Self::InvalidNumRounds {
+                num_rounds,
+                max_num_rounds,
+            } => write!(
+                f,
+                "number of rounds must be between 1 and {max_num_rounds}, got {num_rounds}"
+            ),
+        }
+    }
+}
+
+impl Error for LibraryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TIP5_LIB;
+
+    #[test]
+    fn tip5_library_exports_permutation_procedures() {
+        let library = Tip5Library::default();
+        assert_eq!(Tip5Library::NAMESPACE, library.root_ns().as_str());
+
+        let module = library.modules().next().unwrap();
+        assert_eq!("tip5::permutation", module.path.as_str());
+        let expected_exports = vec![
+            "init",
+            "init_from_advice",
+            "ensure_init",
+            "assert_table_present",
+            "split_and_lookup",
+            "sbox_layer",
+            "mds",
+            "permute",
+            "permute_isolated",
+        ];
+        assert_eq!(expected_exports, exported_procedures(module));
+    }
+
+    #[test]
+    fn tip5_library_exports_sponge_procedures() {
+        let library = Tip5Library::default();
+        let module = library.modules().nth(1).unwrap();
+        assert_eq!("tip5::sponge", module.path.as_str());
+
+        let expected_exports = vec![
+            "init_fixed_length",
+            "init_variable_length",
+            "absorb",
+            "squeeze",
+        ];
+        assert_eq!(expected_exports, exported_procedures(module));
+    }
+
+    #[test]
+    fn tip5_library_exports_hash_procedures() {
+        let library = Tip5Library::default();
+        let module = library.modules().nth(2).unwrap();
+        assert_eq!("tip5::hash", module.path.as_str());
+        let expected_exports = vec!["hash_10", "hash_pair", "hash_varlen"];
+        assert_eq!(expected_exports, exported_procedures(module));
+    }
+
+    #[test]
+    fn tip5_library_exports_tip4_like_procedures_on_request() {
+        let library = Tip5Library::default().with_tip4_like(true);
+        let module = library.modules().nth(3).unwrap();
+        assert_eq!("tip5::tip4_like", module.path.as_str());
+        let expected_exports = vec!["sbox_layer", "mds", "permute"];
+        assert_eq!(expected_exports, exported_procedures(module));
+
+        let module = library.modules().nth(4).unwrap();
+        assert_eq!("tip5::tip4_like_hash", module.path.as_str());
+        let expected_exports = vec!["hash_pair", "hash_varlen"];
+        assert_eq!(expected_exports, exported_procedures(module));
+    }
+
+    #[test]
+    fn default_table_base_is_declared_in_permutation_module() {
+        assert!(TIP5_LIB.contains(&format!(
+            "const.TABLE_BASE={}",
+            Tip5Library::DEFAULT_TABLE_BASE
+        )));
+    }
+
+    #[test]
+    #[should_panic(expected = "lookup table must fit into the address space")]
+    fn table_base_close_to_end_of_address_space_is_rejected() {
+        Tip5Library::default().with_table_base(u32::MAX - 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "lookup table must fit into the address space")]
+    fn sentinel_beyond_end_of_address_space_is_rejected() {
+        Tip5Library::default().with_table_base(u32::MAX - 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of rounds must be between 1 and 16")]
+    fn zero_rounds_are_rejected() {
+        Tip5Library::default().with_num_rounds(0);
+    }
+
+    #[test]
+    fn invalid_options_are_reported_as_errors() {
+        let result = Tip5Library::default().try_with_table_base(u32::MAX - 255);
+        let expected = LibraryError::TableOutOfAddressSpace {
+            table_base: u32::MAX - 255,
+        };
+        assert_eq!(Some(expected), result.err());
+
+        let result = Tip5Library::default().try_with_num_rounds(17);
+        let expected = LibraryError::InvalidNumRounds {
+            num_rounds: 17,
+            max_num_rounds: 16,
+        };
+        assert_eq!(Some(expected), result.err());
+    }
+
+    #[test]
+    fn reduced_round_library_declares_only_the_applied_rounds() {
+        let library = Tip5Library::default().with_num_rounds(2);
+        let module = library.modules().next().unwrap();
+        let procedures = module.ast.local_procs.iter().map(|proc| proc.name.as_ref());
+        let rounds = procedures.filter(|name: &&str| name.starts_with("round_"));
+        assert_eq!(vec!["round_0", "round_1"], rounds.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sources_of_default_library_include_checked_in_permutation_module() {
+        let sources = Tip5Library::default().sources();
+        let paths = sources.iter().map(|(path, _)| *path).collect::<Vec<_>>();
+        let expected_paths = vec!["permutation", "sponge", "hash"];
+        assert_eq!(expected_paths, paths);
+        assert_eq!(TIP5_LIB, sources[0].1);
+    }
+
+    fn exported_procedures(module: &Module) -> Vec<&str> {
+        module
+            .ast
+            .local_procs
+            .iter()
+            .filter(|proc| proc.is_export)
+            .map(|proc| proc.name.as_ref())
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+//! Miden assembly sources of the Tip5 library and of programs using it.
+
+/// The [Tip5](https://eprint.iacr.org/2023/107.pdf) permutation as a Miden module.
+///
+/// The module is made available as `tip5::permutation` by [`Tip5Library`](crate::Tip5Library).
+/// Procedure `init` must be executed once before the first invocation of `permute`, since it
+/// writes the lookup table to memory addresses `TABLE_BASE` through `TABLE_BASE + 255`. The
+/// address `TABLE_BASE` defaults to 0 and can be changed using
+/// [`Tip5Library::with_table_base`](crate::Tip5Library::with_table_base). Procedure
+/// `ensure_init` can be executed any number of times instead: it writes the lookup table only if
+/// the sentinel at address `TABLE_BASE + 256`, which `init` sets, is missing.
+///
+/// Procedure `init_from_advice` writes the same lookup table in fewer cycles by reading it from the
+/// advice stack, which can be populated using [`lookup_table_advice`](crate::lookup_table_advice).
+///
+/// Using [`Tip5Library::with_arithmetic_lookup`](crate::Tip5Library::with_arithmetic_lookup), the
+/// lookup table's entries are computed instead, in which case the permutation does not access
+/// memory and no initialization is required.
+///
+/// Procedure `assert_table_present` fails unless the sentinel is set. Using
+/// [`Tip5Library::with_table_assertions`](crate::Tip5Library::with_table_assertions), it is
+/// executed before every lookup, which helps to catch a missing initialization.
+///
+/// Alternatively, procedure `permute_isolated` can be invoked using `call`, in which case the
+/// lookup table is kept in the procedure's own memory context.
+///
+/// The source is generated from the constants of the reference implementation by
+/// [`permutation_module`](crate::permutation_module).
+pub const TIP5_LIB: &str = include_str!("masm/permutation.masm");
+
+/// The sponge construction on top of the [Tip5](https://eprint.iacr.org/2023/107.pdf)
+/// permutation as a Miden module, compatible with
+/// [`Tip5State`](twenty_first::shared_math::tip5::Tip5State).
+///
+/// The module is made available as `tip5::sponge` by [`Tip5Library`](crate::Tip5Library). All
+/// procedures operate on the 16-element sponge state on top of the stack, with the first element
+/// of the state at the very top. Since `absorb` and `squeeze` invoke the permutation, procedure
+/// `init` of module `tip5::permutation` must have been executed before.
+///
+/// The source is generated by [`sponge_module`](crate::sponge_module).
+pub const TIP5_SPONGE: &str = include_str!("masm/sponge.masm");
+
+/// Hash functions built from the [Tip5](https://eprint.iacr.org/2023/107.pdf) permutation as a
+/// Miden module, compatible with [`Tip5`](twenty_first::shared_math::tip5::Tip5).
+///
+/// The module is made available as `tip5::hash` by [`Tip5Library`](crate::Tip5Library). Since
+/// all procedures invoke the permutation, procedure `init` of module `tip5::permutation` must
+/// have been executed before.
+///
+/// Procedure `hash_varlen` reads its input from memory, one element per address. The input must
+/// not overlap with the lookup table of the permutation.
+///
+/// The source is generated by [`hash_module`](crate::hash_module).
+pub const TIP5_HASH: &str = include_str!("masm/hash.masm");
+
+/// Hash functions built from the Tip4-like permutation as a Miden module, compatible with
+/// [`tip4_like_hash_pair`](crate::tip4_like_hash_pair) and
+/// [`tip4_like_hash_varlen`](crate::tip4_like_hash_varlen) only. The permutation is not Tip4', see
+/// [`tip4_like`](crate::PermutationParameters::tip4_like).
+///
+/// The module is made available as `tip5::tip4_like_hash` by a [`Tip5Library`](crate::Tip5Library)
+/// created using [`with_tip4_like`](crate::Tip5Library::with_tip4_like). The sponge state
+/// consists of 12 elements, the first 8 of which form the rate. Digests consist of 4 elements. Since all procedures invoke the permutation, procedure `init` of module
+/// `tip5::permutation` must have been executed before.
+///
+/// Procedure `hash_varlen` reads its input from memory, one element per address. The input must
+/// not overlap with the lookup table of the permutation.
+///
+/// The source is generated by [`tip4_like_hash_module`](crate::tip4_like_hash_module).
+pub const TIP4_LIKE_HASH: &str = include_str!("masm/tip4_like_hash.masm");
+
+/// A program running the Tip5 permutation once on the 16 elements on top of the stack.
+pub const TIP5_PROGRAM: &str = "
+    use.tip5::permutation
+
+    begin
+        exec.permutation::init
+        exec.permutation::permute
+    end
+";
@@ -0,0 +1,192 @@
+use miden_vm::crypto::Rpo256;
+use miden_vm::math::StarkField;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::MDS_MATRIX_FIRST_COLUMN;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+use twenty_first::shared_math::tip5::NUM_SPLIT_AND_LOOKUP;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+/// The parameters of a permutation from the family [Tip5](https://eprint.iacr.org/2023/107.pdf)
+/// belongs to. Every round applies the S-box layer, multiplies the state with a circulant MDS
+/// matrix, and adds round constants. The S-box layer applies Tip5's split-and-lookup map to the
+/// first state elements and the power map x^7 to the remaining ones.
+///
+/// Both the Miden assembly implementation, see
+/// [`Tip5Library::with_parameters`](crate::Tip5Library::with_parameters), and the reference
+/// implementation, see [`reference_permutation_with`](crate::reference_permutation_with), accept
+/// any parameters. Whether they yield a secure permutation is up to the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermutationParameters {
+    name: String,
+    state_size: usize,
+    num_split_and_lookup: usize,
+    mds_first_column: Vec<BFieldElement>,
+    num_rounds: usize,
+}
+
+impl PermutationParameters {
+    /// The smallest supported state size.
+    pub const MIN_STATE_SIZE: usize = 2;
+
+    /// The largest supported state size. Miden's instructions can only reach the top 16 stack
+    /// elements directly.
+    pub const MAX_STATE_SIZE: usize = 16;
+
+    /// The parameters of a permutation with the given name, which determines its round
+    /// constants. The number of rounds is Tip5's five.
+    ///
+    /// # Panics
+    /// Panics if the state size is less than [`Self::MIN_STATE_SIZE`] or exceeds
+    /// [`Self::MAX_STATE_SIZE`], if the number of split-and-lookup S-boxes exceeds the state size,
+    /// or if the MDS matrix's first column does not have one entry per state element.
+    pub fn new(
+        name: &str,
+        state_size: usize,
+        num_split_and_lookup: usize,
+        mds_first_column: Vec<BFieldElement>,
+    ) -> Self {
+        assert!(
+            (Self::MIN_STATE_SIZE..=Self::MAX_STATE_SIZE).contains(&state_size),
+            "state size must be between {} and {}",
+            Self::MIN_STATE_SIZE,
+            Self::MAX_STATE_SIZE
+        );
+        assert!(
+            num_split_and_lookup <= state_size,
+            "number of split-and-lookup S-boxes must not exceed the state size"
+        );
+        assert_eq!(
+            state_size,
+            mds_first_column.len(),
+            "MDS matrix must have one row per state element"
+        );
+        Self {
+            name: name.to_string(),
+            state_size,
+            num_split_and_lookup,
+            mds_first_column,
+            num_rounds: NUM_ROUNDS,
+        }
+    }
+
+    /// The parameters of Tip5 itself.
+    pub fn tip5() -> Self {
+        let mds_first_column =
+            MDS_MATRIX_FIRST_COLUMN.map(|entry| BFieldElement::new(entry as u64));
+        Self::new(
+            "Tip5",
+            STATE_SIZE,
+            NUM_SPLIT_AND_LOOKUP,
+            mds_first_column.to_vec(),
+        )
+    }
+
+    /// The parameters of a permutation shaped like Tip4', the variant of Tip5 with a 12-element
+    /// state: 4 split-and-lookup S-boxes and 5 rounds. It is *not* Tip4' and must not be used in
+    /// its place. The round constants are derived from the name `"Tip4-like"` like those of Tip5,
+    /// and the MDS matrix is the circulant 12x12 matrix of [`Rpo256`].
+    pub fn tip4_like() -> Self {
+        let mds_first_column = Rpo256::MDS.map(|row| BFieldElement::new(row[0].as_int()));
+        Self::new(
+            "Tip4-like",
+            12,
+            NUM_SPLIT_AND_LOOKUP,
+            mds_first_column.to_vec(),
+        )
+    }
+
+    /// Applies the given number of rounds.
+    ///
+    /// # Panics
+    /// Panics if `num_rounds` is 0 or exceeds [`Self::max_num_rounds`].
+    pub fn with_num_rounds(self, num_rounds: usize) -> Self {
+        let max_num_rounds = self.max_num_rounds();
+        assert!(
+            (1..=max_num_rounds).contains(&num_rounds),
+            "number of rounds must be between 1 and {max_num_rounds}"
+        );
+        Self { num_rounds, ..self }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state_size(&self) -> usize {
+        self.state_size
+    }
+
+    pub fn num_split_and_lookup(&self) -> usize {
+        self.num_split_and_lookup
+    }
+
+    pub fn mds_first_column(&self) -> &[BFieldElement] {
+        &self.mds_first_column
+    }
+
+    pub fn num_rounds(&self) -> usize {
+        self.num_rounds
+    }
+
+    /// The largest supported number of rounds. Round constants are derived from a single byte
+    /// indexing them, which limits their number to 256.
+    pub fn max_num_rounds(&self) -> usize {
+        256 / self.state_size
+    }
+
+    /// The constants added to the state in the given round, derived like Tip5's: the constant
+    /// with index `i` is the BLAKE3 hash of the permutation's name followed by the byte `i`,
+    /// interpreted as a little-endian 128-bit integer and reduced modulo the field's prime, which
+    /// is then taken as the element's Montgomery representation.
+    ///
+    /// # Panics
+    /// Panics if `round` is not less than [`Self::max_num_rounds`].
+    pub fn round_constants(&self, round: usize) -> Vec<BFieldElement> {
+        let max_num_rounds = self.max_num_rounds();
+        assert!(
+            round < max_num_rounds,
+            "round must be less than {max_num_rounds}"
+        );
+        let constant = |i| {
+            let index = (round * self.state_size + i) as u8;
+            let hash = blake3::hash(&[self.name.as_bytes(), &[index]].concat());
+            let integer = u128::from_le_bytes(hash.as_bytes()[..16].try_into().unwrap());
+            BFieldElement::from_raw_u64((integer % BFieldElement::P as u128) as u64)
+        };
+        (0..self.state_size).map(constant).collect()
+    }
+}
+
+impl Default for PermutationParameters {
+    fn default() -> Self {
+        Self::tip5()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::tip5::ROUND_CONSTANTS;
+
+    use super::*;
+
+    #[test]
+    fn tip5_round_constants_agree_with_twenty_first() {
+        let parameters = PermutationParameters::tip5();
+        let round_constants = (0..NUM_ROUNDS).flat_map(|r| parameters.round_constants(r));
+        assert!(round_constants.eq(ROUND_CONSTANTS));
+    }
+
+    #[test]
+    #[should_panic(expected = "number of split-and-lookup S-boxes must not exceed the state size")]
+    fn more_split_and_lookup_s_boxes_than_state_elements_are_rejected() {
+        let mds_first_column = vec![BFieldElement::new(1); 4];
+        PermutationParameters::new("Tiny", 4, 5, mds_first_column);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of rounds must be between 1 and 21")]
+    fn too_many_rounds_for_state_size_are_rejected() {
+        let mds_first_column = vec![BFieldElement::new(1); 12];
+        PermutationParameters::new("Tiny", 12, 4, mds_first_column).with_num_rounds(22);
+    }
+}
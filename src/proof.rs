@@ -0,0 +1,378 @@
+use std::error::Error;
+use std::fmt;
+
+use miden_vm::math::Felt;
+use miden_vm::math::StarkField;
+use miden_vm::prove;
+use miden_vm::verify;
+use miden_vm::Digest;
+use miden_vm::ExecutionError;
+use miden_vm::ExecutionProof;
+use miden_vm::Kernel;
+use miden_vm::MemAdviceProvider;
+use miden_vm::ProgramInfo;
+use miden_vm::ProofOptions;
+use miden_vm::StackInputs;
+use miden_vm::StackOutputs;
+use miden_vm::VerificationError;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+use crate::stack_inputs;
+use crate::stack_outputs;
+use crate::state_from_outputs;
+use crate::tip5_program_with;
+use crate::LibraryError;
+use crate::State;
+use crate::Tip5Library;
+use crate::MAX_NUM_ROUNDS;
+
+/// The hash of the compiled [`TIP5_PROGRAM`](crate::TIP5_PROGRAM), which allows checking that a
+/// proof is about the Tip5 permutation without compiling the program.
+pub const TIP5_PROGRAM_HASH: Digest = Digest::new([
+    Felt::new(6422613573073541961),
+    Felt::new(18018763687789489626),
+    Felt::new(6653127350696024551),
+    Felt::new(1142175035584612122),
+]);
+
+/// A proof of an execution of [`TIP5_PROGRAM`](crate::TIP5_PROGRAM) or one of its variants,
+/// together with the public inputs it was generated for: the program, the stack inputs, and the
+/// stack outputs. Verifying the proof checks exactly the statement that was proven.
+///
+/// The proof also records the number of rounds of the proven permutation, which determines the
+/// program [`Self::verify`] expects.
+#[derive(Debug, Clone)]
+pub struct PermutationProof {
+    num_rounds: usize,
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    proof: ExecutionProof,
+}
+
+impl PermutationProof {
+    /// Combines a proof with the public inputs it claims to be valid for, e.g., after receiving
+    /// them separately, and with the number of rounds of the proven permutation. Fails unless the
+    /// stack inputs and the stack outputs each hold exactly one state, and unless `num_rounds` is
+    /// between 1 and [`MAX_NUM_ROUNDS`].
+    pub fn new(
+        num_rounds: usize,
+        program_info: ProgramInfo,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+        proof: ExecutionProof,
+    ) -> Result<Self, ProofError> {
+        let inputs = stack_inputs.values().len();
+        let outputs = stack_outputs.stack().len();
+        if inputs != STATE_SIZE || outputs != STATE_SIZE {
+            return Err(ProofError::StackDepthMismatch { inputs, outputs });
+        }
+        if !(1..=MAX_NUM_ROUNDS).contains(&num_rounds) {
+            let max_num_rounds = MAX_NUM_ROUNDS;
+            let error = LibraryError::InvalidNumRounds {
+                num_rounds,
+                max_num_rounds,
+            };
+            return Err(error.into());
+        }
+        Ok(Self {
+            num_rounds,
+            program_info,
+            stack_inputs,
+            stack_outputs,
+            proof,
+        })
+    }
+
+    /// The claim that [`TIP5_PROGRAM`](crate::TIP5_PROGRAM) maps `input` to `output`, attested
+    /// by the given proof.
+    pub fn for_states(input: &State, output: &State, proof: ExecutionProof) -> Self {
+        Self {
+            num_rounds: NUM_ROUNDS,
+            program_info: ProgramInfo::new(TIP5_PROGRAM_HASH, Kernel::default()),
+            stack_inputs: stack_inputs(input),
+            stack_outputs: stack_outputs(output),
+            proof,
+        }
+    }
+
+    /// The number of rounds of the proven permutation.
+    pub fn num_rounds(&self) -> usize {
+        self.num_rounds
+    }
+
+    pub fn program_info(&self) -> &ProgramInfo {
+        &self.program_info
+    }
+
+    pub fn stack_inputs(&self) -> &StackInputs {
+        &self.stack_inputs
+    }
+
+    pub fn stack_outputs(&self) -> &StackOutputs {
+        &self.stack_outputs
+    }
+
+    pub fn proof(&self) -> &ExecutionProof {
+        &self.proof
+    }
+
+    /// The state the permutation was applied to.
+    pub fn input_state(&self) -> State {
+        let values = self.stack_inputs.values();
+        std::array::from_fn(|i| BFieldElement::new(values[i].as_int()))
+    }
+
+    /// The state the permutation is claimed to result in.
+    pub fn output_state(&self) -> State {
+        state_from_outputs(&self.stack_outputs)
+    }
+
+    /// Verifies that [`TIP5_PROGRAM`](crate::TIP5_PROGRAM), compiled against the default
+    /// [`Tip5Library`] with the recorded number of rounds, maps the stack inputs to the stack
+    /// outputs. Returns the security level of the proof in bits.
+    ///
+    /// For Tip5's five rounds, the program hash is compared against [`TIP5_PROGRAM_HASH`].
+    /// Otherwise, the program is compiled to obtain its hash.
+    pub fn verify(&self) -> Result<u32, ProofError> {
+        let expected = match self.num_rounds {
+            NUM_ROUNDS => TIP5_PROGRAM_HASH,
+            num_rounds => {
+                let library = Tip5Library::default().try_with_num_rounds(num_rounds)?;
+                tip5_program_with(&library).hash()
+            }
+        };
+        self.verify_for(expected)
+    }
+
+    /// Verifies that the program with the given hash maps the stack inputs to the stack outputs,
+    /// e.g., a program compiled by [`tip5_program_with`](crate::tip5_program_with). Returns the
+    /// security level of the proof in bits.
+    pub fn verify_for(&self, expected: Digest) -> Result<u32, ProofError> {
+        let actual = *self.program_info.program_hash();
+        if expected != actual {
+            return Err(ProofError::ProgramMismatch { expected, actual });
+        }
+        let security_level = verify(
+            self.program_info.clone(),
+            self.stack_inputs.clone(),
+            self.stack_outputs.clone(),
+            self.proof.clone(),
+        )?;
+        Ok(security_level)
+    }
+}
+
+/// The reasons proving or verifying an execution of the Tip5 permutation can fail.
+#[derive(Debug)]
+pub enum ProofError {
+    /// The program failed to execute.
+    Execution(ExecutionError),
+
+    /// The proof is for a program other than the expected one.
+    ProgramMismatch { expected: Digest, actual: Digest },
+
+    /// The recorded number of rounds is not supported by the library.
+    Library(LibraryError),
+
+    /// The stack inputs or the stack outputs do not hold exactly one state.
+    StackDepthMismatch { inputs: usize, outputs: usize },
+
+    /// The proof does not attest to the given program mapping the stack inputs to the outputs.
+    Verification(VerificationError),
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Execution(e) => write!(f, "execution failed: {e}"),
+            Self::ProgramMismatch { expected, actual } => write!(
+                f,
+                "proof is for program {}, expected program {}",
+                hex::encode(actual.as_bytes()),
+                hex::encode(expected.as_bytes())
+            ),
+            Self::Library(e) => write!(f, "{e}"),
+            Self::StackDepthMismatch { inputs, outputs } => write!(
+                f,
+                "expected {STATE_SIZE} stack inputs and outputs, got {inputs} inputs and \
+                {outputs} outputs"
+            ),
+            Self::Verification(e) => write!(f, "verification failed: {e}"),
+        }
+    }
+}
+
+impl Error for ProofError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Execution(e) => Some(e),
+            Self::ProgramMismatch { .. } => None,
+            Self::Library(e) => Some(e),
+            Self::StackDepthMismatch { .. } => None,
+            Self::Verification(e) => Some(e),
+        }
+    }
+}
+
+impl From<ExecutionError> for ProofError {
+    fn from(error: ExecutionError) -> Self {
+        Self::Execution(error)
+    }
+}
+
+impl From<LibraryError> for ProofError {
+    fn from(error: LibraryError) -> Self {
+        Self::Library(error)
+    }
+}
+
+impl From<VerificationError> for ProofError {
+    fn from(error: VerificationError) -> Self {
+        Self::Verification(error)
+    }
+}
+
+/// Executes the Tip5 permutation on the Miden VM and proves correct execution.
+pub fn prove_permutation(
+    state: State,
+    options: ProofOptions,
+) -> Result<PermutationProof, ProofError> {
+    prove_permutation_with(&Tip5Library::default(), state, options)
+}
+
+/// Executes the permutation of the given [`Tip5Library`] on the Miden VM and proves correct
+/// execution. The proof records the hash of the program compiled against the library, see
+/// [`tip5_program_with`], and the library's number of rounds. Unless the library differs from the
+/// default one in other options, the proof verifies using [`PermutationProof::verify`].
+/// Otherwise, use [`PermutationProof::verify_for`] with the program's hash.
+pub fn prove_permutation_with(
+    library: &Tip5Library,
+    state: State,
+    options: ProofOptions,
+) -> Result<PermutationProof, ProofError> {
+    let program = tip5_program_with(library);
+    let program_info = ProgramInfo::new(program.hash(), program.kernel().clone());
+    let stack_inputs = stack_inputs(&state);
+    let (stack_outputs, proof) = prove(
+        &program,
+        stack_inputs.clone(),
+        MemAdviceProvider::default(),
+        options,
+    )?;
+    PermutationProof::new(
+        library.num_rounds(),
+        program_info,
+        stack_inputs,
+        stack_outputs,
+        proof,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference_permutation_with;
+    use crate::test_helpers::sample_state;
+    use crate::test_helpers::twenty_first_permutation;
+    use crate::tip5_program;
+
+    #[test]
+    fn tip5_program_hash_matches_compiled_program() {
+        let program = tip5_program();
+        assert_eq!(TIP5_PROGRAM_HASH, program.hash());
+        assert_eq!(&Kernel::default(), program.kernel());
+    }
+
+    #[test]
+    fn proof_verifies_exactly_the_proven_statement() {
+        let state = sample_state(1);
+        let proof = prove_permutation(state, ProofOptions::default()).unwrap();
+        assert_eq!(state, proof.input_state());
+        let expected_state = twenty_first_permutation(state);
+        assert_eq!(expected_state, proof.output_state());
+        assert!(proof.verify().is_ok());
+
+        let claim = PermutationProof::for_states(&state, &expected_state, proof.proof.clone());
+        assert!(claim.verify().is_ok());
+
+        let other_inputs = PermutationProof {
+            stack_inputs: stack_inputs(&sample_state(2)),
+            ..proof.clone()
+        };
+        let result = other_inputs.verify();
+        assert!(matches!(result, Err(ProofError::Verification(_))));
+
+        let mut stack_outputs = proof.stack_outputs.clone();
+        stack_outputs.stack_mut()[0] += 1;
+        let other_outputs = PermutationProof {
+            stack_outputs,
+            ..proof.clone()
+        };
+        let result = other_outputs.verify();
+        assert!(matches!(result, Err(ProofError::Verification(_))));
+
+        let reduced_round_program = tip5_program_with(&Tip5Library::default().with_num_rounds(1));
+        let program_info = ProgramInfo::new(reduced_round_program.hash(), Kernel::default());
+        let other_program = PermutationProof {
+            program_info,
+            ..proof
+        };
+        let result = other_program.verify();
+        assert!(matches!(result, Err(ProofError::ProgramMismatch { .. })));
+        let result = other_program.verify_for(reduced_round_program.hash());
+        assert!(matches!(result, Err(ProofError::Verification(_))));
+
+        let result = PermutationProof::new(
+            NUM_ROUNDS,
+            other_program.program_info.clone(),
+            StackInputs::default(),
+            other_program.stack_outputs.clone(),
+            other_program.proof.clone(),
+        );
+        assert!(matches!(
+            result,
+            Err(ProofError::StackDepthMismatch {
+                inputs: 0,
+                outputs: STATE_SIZE
+            })
+        ));
+
+        let result = PermutationProof::new(
+            0,
+            other_program.program_info,
+            other_program.stack_inputs,
+            other_program.stack_outputs,
+            other_program.proof,
+        );
+        assert!(matches!(
+            result,
+            Err(ProofError::Library(LibraryError::InvalidNumRounds { .. }))
+        ));
+    }
+
+    #[test]
+    fn proofs_of_reduced_and_extended_round_permutations_verify() {
+        let state = sample_state(1);
+        for num_rounds in [1, 8] {
+            let library = Tip5Library::default().with_num_rounds(num_rounds);
+            let options = ProofOptions::default();
+            let proof = prove_permutation_with(&library, state, options).unwrap();
+            let expected_state = reference_permutation_with(library.parameters(), &state);
+            assert_eq!(expected_state, proof.output_state().to_vec());
+
+            let program_hash = tip5_program_with(&library).hash();
+            assert!(proof.verify_for(program_hash).is_ok());
+            assert!(proof.verify().is_ok());
+
+            let other_num_rounds = PermutationProof {
+                num_rounds: NUM_ROUNDS,
+                ..proof
+            };
+            let result = other_num_rounds.verify();
+            assert!(matches!(result, Err(ProofError::ProgramMismatch { .. })));
+        }
+    }
+}
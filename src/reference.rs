@@ -0,0 +1,174 @@
+//! A Rust reference implementation of Tip5-like permutations, for comparison with the Miden
+//! assembly implementation of reduced-round, extended-round, and otherwise modified variants of
+//! Tip5. With Tip5's parameters, it agrees with [`Tip5`](twenty_first::shared_math::tip5::Tip5).
+
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::LOOKUP_TABLE;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+use crate::PermutationParameters;
+use crate::State;
+
+/// The largest supported number of rounds of Tip5, see
+/// [`PermutationParameters::max_num_rounds`].
+pub const MAX_NUM_ROUNDS: usize = 256 / STATE_SIZE;
+
+/// The number of elements absorbed by every invocation of the Tip4-like permutation
+/// when hashing, see [`PermutationParameters::tip4_like`].
+pub const TIP4_LIKE_RATE: usize = 8;
+
+/// The number of elements of a digest of the Tip4-like hash functions.
+pub const TIP4_LIKE_DIGEST_LENGTH: usize = 4;
+
+/// The constants added to the state in the given round of Tip5, see
+/// [`PermutationParameters::round_constants`].
+///
+/// # Panics
+/// Panics if `round` is not less than [`MAX_NUM_ROUNDS`].
+pub fn round_constants(round: usize) -> State {
+    let round_constants = PermutationParameters::tip5().round_constants(round);
+    round_constants.try_into().unwrap()
+}
+
+/// The state after applying the given number of rounds of the Tip5 permutation.
+///
+/// # Panics
+/// Panics if `num_rounds` is 0 or exceeds [`MAX_NUM_ROUNDS`].
+pub fn reference_permutation(state: State, num_rounds: usize) -> State {
+    *reference_trace(state, num_rounds).last().unwrap()
+}
+
+/// The initial state followed by the state after each of the given number of rounds, like
+/// [`Tip5::trace`](twenty_first::shared_math::tip5::Tip5::trace).
+///
+/// # Panics
+/// Panics if `num_rounds` is 0 or exceeds [`MAX_NUM_ROUNDS`].
+pub fn reference_trace(state: State, num_rounds: usize) -> Vec<State> {
+    let parameters = PermutationParameters::tip5().with_num_rounds(num_rounds);
+    let trace = reference_trace_with(&parameters, &state);
+    trace.into_iter().map(|s| s.try_into().unwrap()).collect()
+}
+
+/// The state after applying the permutation with the given parameters.
+///
+/// # Panics
+/// Panics if the state's size differs from the parameters'.
+pub fn reference_permutation_with(
+    parameters: &PermutationParameters,
+    state: &[BFieldElement],
+) -> Vec<BFieldElement> {
+    reference_trace_with(parameters, state).pop().unwrap()
+}
+
+/// The initial state followed by the state after each round of the permutation with the given
+/// parameters.
+///
+/// # Panics
+/// Panics if the state's size differs from the parameters'.
+pub fn reference_trace_with(
+    parameters: &PermutationParameters,
+    state: &[BFieldElement],
+) -> Vec<Vec<BFieldElement>> {
+    assert_eq!(
+        parameters.state_size(),
+        state.len(),
+        "state must have the parameters' size"
+    );
+    let mut trace = vec![state.to_vec()];
+    for round in 0..parameters.num_rounds() {
+        let mut state = mds(parameters, &sbox_layer(parameters, &trace[round]));
+        let constants = parameters.round_constants(round);
+        state.iter_mut().zip(constants).for_each(|(s, c)| *s += c);
+        trace.push(state);
+    }
+    trace
+}
+
+/// The hash of the two given digests using the Tip4-like permutation, see
+/// [`PermutationParameters::tip4_like`]. It is not compatible with Tip4'. Like Tip5's
+/// `hash_pair`, the capacity is set to all ones and the digest is read from the first elements of
+/// the rate.
+pub fn tip4_like_hash_pair(
+    left: &[BFieldElement; TIP4_LIKE_DIGEST_LENGTH],
+    right: &[BFieldElement; TIP4_LIKE_DIGEST_LENGTH],
+) -> [BFieldElement; TIP4_LIKE_DIGEST_LENGTH] {
+    let parameters = PermutationParameters::tip4_like();
+    let capacity = [BFieldElement::new(1); 4];
+    let state = [left.as_slice(), right, &capacity].concat();
+    let state = reference_permutation_with(&parameters, &state);
+    state[..TIP4_LIKE_DIGEST_LENGTH].try_into().unwrap()
+}
+
+/// The hash of input of any length using the Tip4-like permutation, see
+/// [`PermutationParameters::tip4_like`]. It is not compatible with Tip4'. Like Tip5's
+/// `hash_varlen`, the input is padded with a 1 followed by 0s to the next multiple of the rate,
+/// the capacity is initially set to all zeros, and every chunk of the padded input is added to
+/// the rate before applying the permutation.
+pub fn tip4_like_hash_varlen(input: &[BFieldElement]) -> [BFieldElement; TIP4_LIKE_DIGEST_LENGTH] {
+    let parameters = PermutationParameters::tip4_like();
+    let padded_length = (input.len() / TIP4_LIKE_RATE + 1) * TIP4_LIKE_RATE;
+    let mut padded_input = input.to_vec();
+    padded_input.push(BFieldElement::new(1));
+    padded_input.resize(padded_length, BFieldElement::new(0));
+
+    let mut state = vec![BFieldElement::new(0); parameters.state_size()];
+    for chunk in padded_input.chunks(TIP4_LIKE_RATE) {
+        state.iter_mut().zip(chunk).for_each(|(s, &c)| *s += c);
+        state = reference_permutation_with(&parameters, &state);
+    }
+    state[..TIP4_LIKE_DIGEST_LENGTH].try_into().unwrap()
+}
+
+fn sbox_layer(parameters: &PermutationParameters, state: &[BFieldElement]) -> Vec<BFieldElement> {
+    let (lookups, powers) = state.split_at(parameters.num_split_and_lookup());
+    let lookups = lookups.iter().map(|&element| split_and_lookup(element));
+    let powers = powers.iter().map(|element| element.mod_pow(7));
+    lookups.chain(powers).collect()
+}
+
+/// The byte-wise lookup of Tip5's S-box, applied to the element's Montgomery representation like
+/// in `twenty_first`, which does not expose it.
+pub(crate) fn split_and_lookup(element: BFieldElement) -> BFieldElement {
+    let bytes = element.raw_bytes().map(|byte| LOOKUP_TABLE[byte as usize]);
+    BFieldElement::from_raw_bytes(&bytes)
+}
+
+/// Multiplies the state with the circulant MDS matrix.
+fn mds(parameters: &PermutationParameters, state: &[BFieldElement]) -> Vec<BFieldElement> {
+    let column = parameters.mds_first_column();
+    let size = state.len();
+    let row = |k| {
+        (0..size)
+            .map(|j| column[(size + k - j) % size] * state[j])
+            .sum()
+    };
+    (0..size).map(row).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::tip5::Tip5;
+    use twenty_first::shared_math::tip5::Tip5State;
+    use twenty_first::shared_math::tip5::NUM_ROUNDS;
+
+    use super::*;
+
+    #[test]
+    fn full_round_trace_agrees_with_twenty_first() {
+        let state: State = std::array::from_fn(|i| BFieldElement::new(i as u64 * 1_000_003));
+        let expected_trace = Tip5::trace(&mut Tip5State { state });
+        assert_eq!(expected_trace.to_vec(), reference_trace(state, NUM_ROUNDS));
+    }
+
+    #[test]
+    #[should_panic(expected = "number of rounds must be between 1 and 16")]
+    fn too_many_rounds_are_rejected() {
+        reference_permutation(State::default(), MAX_NUM_ROUNDS + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "state must have the parameters' size")]
+    fn state_of_wrong_size_is_rejected() {
+        reference_permutation_with(&PermutationParameters::tip5(), &[BFieldElement::new(1); 12]);
+    }
+}
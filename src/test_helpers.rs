@@ -0,0 +1,19 @@
+//! Fixtures shared by the tests of several modules.
+
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::Tip5;
+use twenty_first::shared_math::tip5::Tip5State;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+
+use crate::State;
+
+/// The state whose elements are `offset`, `offset + 1`, and so on.
+pub(crate) fn sample_state(offset: u64) -> State {
+    std::array::from_fn(|i| BFieldElement::new(offset + i as u64))
+}
+
+/// The state after applying the Tip5 permutation of [`twenty_first`], against which every
+/// implementation with Tip5's own parameters is compared.
+pub(crate) fn twenty_first_permutation(state: State) -> State {
+    Tip5::trace(&mut Tip5State { state })[NUM_ROUNDS]
+}
@@ -0,0 +1,274 @@
+use miden_stdlib::StdLibrary;
+use miden_vm::execute;
+use miden_vm::AdviceInputs;
+use miden_vm::Assembler;
+use miden_vm::ExecutionError;
+use miden_vm::MemAdviceProvider;
+use miden_vm::Program;
+use miden_vm::StackInputs;
+use miden_vm::StackOutputs;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::DIGEST_LENGTH;
+use twenty_first::shared_math::tip5::LOOKUP_TABLE;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+
+use crate::Tip5Library;
+use crate::TIP5_PROGRAM;
+
+/// The state of the Tip5 sponge, in the element order used by
+/// [`twenty_first`](twenty_first::shared_math::tip5::Tip5State).
+pub type State = [BFieldElement; STATE_SIZE];
+
+/// An assembler with both the Miden standard library and the default [`Tip5Library`] available.
+pub fn assembler() -> Assembler {
+    assembler_with(&Tip5Library::default())
+}
+
+/// An assembler with both the Miden standard library and the given [`Tip5Library`] available.
+pub fn assembler_with(library: &Tip5Library) -> Assembler {
+    Assembler::default()
+        .with_library(&StdLibrary::default())
+        .expect("standard library must be valid")
+        .with_library(library)
+        .expect("Tip5 library must be valid")
+}
+
+/// The compiled [`TIP5_PROGRAM`].
+pub fn tip5_program() -> Program {
+    tip5_program_with(&Tip5Library::default())
+}
+
+/// The [`TIP5_PROGRAM`] compiled against the given [`Tip5Library`], e.g., one applying a reduced
+/// number of rounds.
+pub fn tip5_program_with(library: &Tip5Library) -> Program {
+    assembler_with(library)
+        .compile(TIP5_PROGRAM)
+        .expect("Tip5 program must compile")
+}
+
+/// Stack inputs placing the given state on top of the stack, with `state[0]` at the very top.
+pub fn stack_inputs(state: &State) -> StackInputs {
+    let values = state.iter().rev().map(|element| element.value());
+    StackInputs::try_from_values(values).expect("canonical field elements must be valid inputs")
+}
+
+/// Stack outputs consisting of the given state only, with `state[0]` at the very top, like those
+/// of an execution of [`TIP5_PROGRAM`].
+pub fn stack_outputs(state: &State) -> StackOutputs {
+    StackOutputs::new(
+        state.iter().map(|element| element.value()).collect(),
+        vec![],
+    )
+}
+
+/// The state on top of the stack, with the topmost element being `state[0]`.
+pub fn state_from_outputs(outputs: &StackOutputs) -> State {
+    let stack = outputs.stack();
+    std::array::from_fn(|i| BFieldElement::new(stack[i]))
+}
+
+/// Advice inputs holding the lookup table in the format expected by procedure `init_from_advice`
+/// of [`TIP5_LIB`](crate::TIP5_LIB): every entry is padded to a word with three zeros.
+pub fn lookup_table_advice() -> AdviceInputs {
+    let values = LOOKUP_TABLE
+        .iter()
+        .flat_map(|&entry| [entry.into(), 0, 0, 0]);
+    AdviceInputs::default()
+        .with_stack_values(values)
+        .expect("table entries must be valid field elements")
+}
+
+/// Executes the Tip5 permutation on the Miden VM.
+pub fn permute(state: State) -> Result<State, ExecutionError> {
+    let program = tip5_program();
+    let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default())?;
+    Ok(state_from_outputs(trace.stack_outputs()))
+}
+
+/// Computes Tip5's fixed-length hash of the given 10 elements on the Miden VM, using procedure
+/// `hash_10` of [`TIP5_HASH`](crate::TIP5_HASH).
+pub fn hash_10(
+    input: &[BFieldElement; 10],
+) -> Result<[BFieldElement; DIGEST_LENGTH], ExecutionError> {
+    let source = "
+        use.tip5::hash
+        use.tip5::permutation
+
+        begin
+            exec.permutation::init
+            exec.hash::hash_10
+        end
+    ";
+    let values = input.iter().rev().map(|element| element.value());
+    let stack_inputs = StackInputs::try_from_values(values)
+        .expect("canonical field elements must be valid inputs");
+    execute_hash(source, stack_inputs, AdviceInputs::default())
+}
+
+/// Computes Tip5's variable-length hash of the given elements on the Miden VM, using procedure
+/// `hash_varlen` of [`TIP5_HASH`](crate::TIP5_HASH). The input's length is passed on the stack
+/// and its elements via the advice stack, so the program does not depend on the input. The
+/// program stores the elements in memory right after the lookup table's sentinel.
+pub fn hash_varlen(
+    input: &[BFieldElement],
+) -> Result<[BFieldElement; DIGEST_LENGTH], ExecutionError> {
+    let ptr = Tip5Library::default().sentinel_address() + 1;
+    let source = format!(
+        "
+        use.tip5::hash
+        use.tip5::permutation
+
+        begin
+            exec.permutation::init
+            push.{ptr} dup.1 push.{ptr} add     # _ len ptr end
+            dup.1 dup.1 neq
+            while.true                          # _ len address end
+                adv_push.1 dup.2 mem_store
+                swap add.1 swap
+                dup.1 dup.1 neq
+            end
+            drop drop push.{ptr}                # _ len ptr
+            exec.hash::hash_varlen
+        end
+        "
+    );
+    let stack_inputs = StackInputs::try_from_values([input.len() as u64])
+        .expect("input length must be a valid input");
+    let values = input.iter().map(|element| element.value());
+    let advice_inputs = AdviceInputs::default()
+        .with_stack_values(values)
+        .expect("canonical field elements must be valid advice");
+    execute_hash(&source, stack_inputs, advice_inputs)
+}
+
+/// Executes the given program, which leaves a digest on top of the stack.
+fn execute_hash(
+    source: &str,
+    stack_inputs: StackInputs,
+    advice_inputs: AdviceInputs,
+) -> Result<[BFieldElement; DIGEST_LENGTH], ExecutionError> {
+    let program = assembler()
+        .compile(source)
+        .expect("hash program must compile");
+    let advice_provider = MemAdviceProvider::from(advice_inputs);
+    let trace = execute(&program, stack_inputs, advice_provider)?;
+    let stack = trace.stack_outputs().stack();
+    Ok(std::array::from_fn(|i| BFieldElement::new(stack[i])))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_vm::crypto::Rpo256;
+    use miden_vm::math::Felt;
+    use miden_vm::math::FieldElement;
+    use miden_vm::math::StarkField;
+    use miden_vm::ExecutionError;
+    use twenty_first::shared_math::tip5::Tip5;
+    use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+
+    use super::*;
+    use crate::test_helpers::sample_state;
+    use crate::test_helpers::twenty_first_permutation;
+    use crate::TIP5_LIB;
+
+    #[test]
+    fn compliance() {
+        let state = sample_state(1);
+        let expected_state = twenty_first_permutation(state);
+
+        assert_eq!(expected_state, permute(state).unwrap());
+    }
+
+    #[test]
+    fn compliance_of_hash_10() {
+        let program = tip5_program();
+
+        // The top of the stack is the first element of the sponge state. The input is absorbed
+        // into the rate, the capacity is initialized to all ones for fixed-length hashing.
+        let stack_inputs =
+            StackInputs::try_from_values([1, 1, 1, 1, 1, 1, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1])
+                .unwrap();
+        let advice_provider = MemAdviceProvider::default();
+        let trace = execute(&program, stack_inputs, advice_provider).unwrap();
+        let public_output = trace.stack_outputs().stack();
+
+        let input = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10].map(BFieldElement::new);
+        let expected_digest = Tip5::hash_10(&input).map(|e| e.value());
+        assert_eq!(expected_digest, public_output[..DIGEST_LENGTH]);
+    }
+
+    #[test]
+    fn hash_10_agrees_with_twenty_first() {
+        let input = std::array::from_fn(|i| BFieldElement::new(i as u64 * 7));
+        assert_eq!(Tip5::hash_10(&input), hash_10(&input).unwrap());
+    }
+
+    #[test]
+    fn hash_varlen_agrees_with_twenty_first() {
+        for length in [0, 10, 13] {
+            let input = (0..length)
+                .map(|i| BFieldElement::new(i * 11))
+                .collect::<Vec<_>>();
+            let expected_digest = Tip5::hash_varlen(&input).values();
+            assert_eq!(
+                expected_digest,
+                hash_varlen(&input).unwrap(),
+                "length: {length}"
+            );
+        }
+    }
+
+    #[test]
+    fn state_survives_round_trip_through_stack() {
+        let program = assembler().compile("begin push.0 drop end").unwrap();
+        let state = sample_state(100);
+        let trace = execute(&program, stack_inputs(&state), MemAdviceProvider::default()).unwrap();
+
+        assert_eq!(100, trace.stack_outputs().stack()[0]);
+        assert_eq!(state, state_from_outputs(trace.stack_outputs()));
+        assert_eq!(&stack_outputs(&state), trace.stack_outputs());
+    }
+
+    #[test]
+    fn table_commitment_in_permutation_module_matches_lookup_table_advice() {
+        let commitment = Rpo256::hash_elements(lookup_table_advice().stack());
+        let commitment = commitment.as_elements().iter().map(|e| e.as_int());
+        let push_commitment = commitment
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        assert!(TIP5_LIB.contains(&format!("push.{push_commitment}\n")));
+    }
+
+    #[test]
+    fn permutation_agrees_with_twenty_first_after_init_from_advice() {
+        let source = "
+            use.tip5::permutation
+
+            begin
+                exec.permutation::init_from_advice
+                exec.permutation::permute
+            end
+        ";
+        let program = assembler().compile(source).unwrap();
+        let state = sample_state(0);
+        let advice_provider = MemAdviceProvider::from(lookup_table_advice());
+        let trace = execute(&program, stack_inputs(&state), advice_provider).unwrap();
+
+        let expected_state = twenty_first_permutation(state);
+        assert_eq!(expected_state, state_from_outputs(trace.stack_outputs()));
+    }
+
+    #[test]
+    fn tampered_lookup_table_advice_is_rejected() {
+        let source = "use.tip5::permutation begin exec.permutation::init_from_advice end";
+        let program = assembler().compile(source).unwrap();
+        let mut advice_stack = lookup_table_advice().stack().to_vec();
+        advice_stack[4 * 100] += Felt::ONE;
+        let advice_inputs = AdviceInputs::default().with_stack(advice_stack);
+
+        let advice_provider = MemAdviceProvider::from(advice_inputs);
+        let result = execute(&program, StackInputs::default(), advice_provider);
+        assert!(matches!(result, Err(ExecutionError::FailedAssertion(_))));
+    }
+}
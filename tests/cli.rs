@@ -0,0 +1,150 @@
+//! Runs the command-line interface and checks its output against the reference implementation in
+//! [`twenty_first`].
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5::Tip5;
+use twenty_first::shared_math::tip5::Tip5State;
+use twenty_first::shared_math::tip5::NUM_ROUNDS;
+use twenty_first::shared_math::tip5::STATE_SIZE;
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+use zkhack_lisbon::reference_permutation;
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zkhack-lisbon"))
+        .args(args)
+        .output()
+        .expect("binary must run")
+}
+
+/// The standard output of a successful run.
+fn run_successfully(args: &[&str]) -> String {
+    successful_output(args, run(args))
+}
+
+fn successful_output(args: &[&str], output: Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{args:?} failed: {stderr}");
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn format_elements(elements: &[BFieldElement]) -> String {
+    let values = elements.iter().map(|element| element.value().to_string());
+    values.collect::<Vec<_>>().join(",")
+}
+
+fn elements(length: usize) -> Vec<BFieldElement> {
+    (0..length as u64)
+        .map(|i| BFieldElement::new(i * 1_000_003))
+        .collect()
+}
+
+/// A path in the temporary directory that no other test uses.
+fn temporary_path(name: &str) -> PathBuf {
+    let file_name = format!("zkhack-lisbon-{}-{name}", std::process::id());
+    std::env::temp_dir().join(file_name)
+}
+
+#[test]
+fn permute_agrees_with_twenty_first() {
+    let state: [_; STATE_SIZE] = elements(STATE_SIZE).try_into().unwrap();
+    let expected_state = Tip5::trace(&mut Tip5State { state })[NUM_ROUNDS];
+    let output = run_successfully(&["permute", &format_elements(&state)]);
+    assert_eq!(format_elements(&expected_state), output);
+}
+
+#[test]
+fn hash_agrees_with_twenty_first() {
+    let input: [_; 10] = elements(10).try_into().unwrap();
+    let output = run_successfully(&["hash", &format_elements(&input)]);
+    assert_eq!(format_elements(&Tip5::hash_10(&input)), output);
+
+    let input = elements(13);
+    let output = run_successfully(&["hash", "--varlen", &format_elements(&input)]);
+    assert_eq!(format_elements(&Tip5::hash_varlen(&input).values()), output);
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let non_canonical = BFieldElement::P.to_string();
+    let too_short = format_elements(&elements(STATE_SIZE - 1));
+    let too_long = format_elements(&elements(11));
+    let invalid_invocations = [
+        vec!["permute", &non_canonical],
+        vec!["permute", "one"],
+        vec!["permute", &too_short],
+        vec!["hash", &too_long],
+        vec!["hash", "--varlen", &non_canonical],
+    ];
+    for args in invalid_invocations {
+        let output = run(&args);
+        assert!(!output.status.success(), "{args:?} succeeded");
+        assert!(output.stdout.is_empty(), "{args:?} printed output");
+    }
+}
+
+#[test]
+fn proof_verifies_only_for_the_proven_states() {
+    let state = elements(STATE_SIZE);
+    let input = format_elements(&state);
+    let path = temporary_path("proof");
+    let path = path.to_str().unwrap();
+    let output = run_successfully(&["prove", "--proof", path, &input]);
+
+    let state: [_; STATE_SIZE] = state.try_into().unwrap();
+    let expected_state = Tip5::trace(&mut Tip5State { state })[NUM_ROUNDS];
+    assert_eq!(format_elements(&expected_state), output);
+    let verify = [
+        "verify", "--proof", path, "--input", &input, "--output", &output,
+    ];
+    run_successfully(&verify);
+
+    let other_input = format_elements(&[BFieldElement::new(1); STATE_SIZE]);
+    let result = run(&["verify", "--proof", path, "--input", &other_input]);
+    assert_eq!(Some(1), result.status.code());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn bundles_of_reduced_round_proofs_verify_with_both_verifiers() {
+    let state = elements(STATE_SIZE);
+    let input = format_elements(&state);
+    let path = temporary_path("reduced-round-bundle");
+    let path = path.to_str().unwrap();
+    let prove = [
+        "prove",
+        "--json",
+        "--num-rounds",
+        "3",
+        "--proof",
+        path,
+        &input,
+    ];
+    let output = run_successfully(&prove);
+
+    let state: [_; STATE_SIZE] = state.try_into().unwrap();
+    let expected_state = format_elements(&reference_permutation(state, 3));
+    assert_eq!(expected_state, output);
+    let verify = [
+        "verify", "--proof", path, "--input", &input, "--output", &output,
+    ];
+    run_successfully(&verify);
+
+    let args = [path];
+    let verify_bundle = Command::new(env!("CARGO_BIN_EXE_verify-bundle"))
+        .args(args)
+        .output()
+        .expect("binary must run");
+    let report = successful_output(&args, verify_bundle);
+    assert!(report.contains("Rounds:          3\n"), "{report}");
+    assert!(
+        report.contains(&format!("Output state:    {output}\n")),
+        "{report}"
+    );
+
+    std::fs::remove_file(path).unwrap();
+}